@@ -0,0 +1,5 @@
+// The cards under `config` are embedded into the binary by `include_dir!`, which cargo doesn't track. Rebuild whenever
+// a card is added, changed or removed.
+fn main() {
+    println!("cargo:rerun-if-changed=config");
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::io::{Stdout, stdout};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use color_eyre::eyre::Result;
 use crossterm::event::{KeyEvent, EventStream, KeyCode};
@@ -12,6 +13,8 @@ use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use futures::stream::StreamExt;
 use tracing::{debug, info};
 use crate::component::Component;
+use crate::core::game::GameSetup;
+use crate::core::save::finish_session;
 
 /// APP is the main engine to run the terminal application. It has UI thread and Data thread.
 /// - UI thread: keyboard event listener and render event generator.
@@ -44,7 +47,12 @@ impl App {
             components: HashMap::new(),
             cur_component: Arc::new(Mutex::new(String::from("HomePage"))),
         };
-        return Ok(app);
+        Ok(app)
+    }
+
+    /// Set the first page to render. The default is the home page.
+    pub fn start_at(&mut self, component_name: &str) {
+        *self.cur_component.lock().unwrap() = component_name.to_string();
     }
 
     pub async fn run(&mut self) -> Result<()> {
@@ -130,7 +138,7 @@ impl App {
                             // 2024-06-03T03:28:59.123166Z DEBUG src/app.rs:106: Data thread received event Render("SelectBossPage")
                             // 2024-06-03T03:28:59.123180Z DEBUG src/app.rs:114: Data thread executing action Render("SelectBossPage")
                             // 2024-06-03T03:28:59.123185Z DEBUG src/app.rs:117: Component changed from GamePage to SelectBossPage
-                            while let Ok(_) = self.event_receiver.try_recv() {}
+                            while self.event_receiver.try_recv().is_ok() {}
                         }
                         if let Some(component) = self.components.get_mut(&*cur_component_name) {
                             self.terminal.draw(|frame| component.draw(frame, frame.size()).unwrap())?;
@@ -139,10 +147,13 @@ impl App {
                             std::process::exit(1);
                         }
                     }
-                    Action::Update(ref component_name, ref message) => {
+                    Action::Update(ref component_name, _)
+                    | Action::Load(ref component_name, _)
+                    | Action::NewGame(ref component_name, _)
+                    | Action::Reset(ref component_name) => {
                         debug!("Data thread executing action {:?}", &action);
                         if let Some(component) = self.components.get_mut(component_name) {
-                            component.update(Action::Update(component_name.clone(), message.clone())).unwrap()
+                            component.update(action.clone()).unwrap()
                         }
                     }
                     Action::Quit => {
@@ -154,12 +165,13 @@ impl App {
     }
 
     pub fn register_component(&mut self, name: String, mut component: Box<dyn Component>) -> Result<()> {
-        if let Some(_) = self.components.get(&name) {
+        if self.components.contains_key(&name) {
             eprintln!("Duplicate component name {}", name);
             std::process::exit(1);
         } else {
             info!("Registering component: {}", &name);
             component.register_action_handler(self.action_sender.clone())?;
+            component.init(self.terminal.size()?)?;
             self.components.insert(name, component);
             Ok(())
         }
@@ -170,6 +182,7 @@ impl App {
             crossterm::execute!(stdout(), LeaveAlternateScreen)?;
             crossterm::terminal::disable_raw_mode()?;
         }
+        finish_session()?;
         std::process::exit(0);
     }
 
@@ -184,9 +197,12 @@ pub enum Event {
     Key(KeyEvent),
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub enum Action {
     Render(String),          // component name
     Quit,
     Update(String, String),  // component name -> message
+    Load(String, PathBuf),   // component name -> save file path
+    NewGame(String, GameSetup), // component name -> options of the new game
+    Reset(String),           // component name
 }
\ No newline at end of file
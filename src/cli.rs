@@ -0,0 +1,255 @@
+use std::io::{stdout, Write};
+use std::path::PathBuf;
+use clap::{Parser, Subcommand, ValueEnum};
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use crate::core::baddie::{Baddie, BADDIE_PATH};
+use crate::core::content::{CardFile, get_content_dir_resolution, list_card_files, read_card};
+use crate::core::export::{ExportFormat, get_export_dir, write_battle_logs};
+use crate::core::game::{CardLibrary, EncounterCard, GameSetup, list_encounter_card_files, pack_name, TyrantCard, TYRANT_CARD_GENERAL_PATH};
+use crate::core::history::get_history_path;
+use crate::core::i18n::{Language, tr};
+use crate::core::party::{Gearloc, GEARLOC_PATH, MAX_PARTY_SIZE};
+use crate::core::save::{get_save_dir, resolve_save, SaveGame};
+use crate::core::settings::get_settings_path;
+use crate::core::validate::validate_content;
+use crate::utils::get_log_path;
+
+/// Command line of the program. Without a subcommand the terminal application starts from the home page.
+/// `play` starts the terminal application too, the other subcommands print to the console and exit.
+#[derive(Debug, Parser)]
+#[command(version, about = "Too Many Bones support program")]
+pub struct Cli {
+    /// Directory that has the `config` directory of the cards. See `diagnostics` for the search order.
+    #[arg(long, global = true)]
+    pub content_dir: Option<PathBuf>,
+    /// Language of the UI and the cards. Defaults to the language chosen on the home page, or Chinese.
+    #[arg(long, global = true, value_enum)]
+    pub lang: Option<Language>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Start the terminal application. Jump straight into the game page when the tyrant is given.
+    Play {
+        /// Tyrant id, e.g. "nom". See `list tyrants`.
+        #[arg(short, long)]
+        tyrant: Option<String>,
+        /// Seed of the encounter deck. A random seed is picked if not given.
+        #[arg(short, long, requires = "tyrant")]
+        seed: Option<u64>,
+        /// Content pack to play with, e.g. "base_set" or "40days". Repeat it for several packs. All packs if not given.
+        #[arg(long = "pack", requires = "tyrant")]
+        packs: Vec<String>,
+        /// Gearloc id of the party, e.g. "boomer". Repeat it for up to 4 Gearlocs. See `list gearlocs`.
+        #[arg(long = "gearloc", requires = "tyrant")]
+        party: Vec<String>,
+    },
+    /// List the tyrants, the encounter cards, the content packs, the Gearlocs or the baddies.
+    List {
+        #[arg(value_enum)]
+        target: ListTarget,
+    },
+    /// Show a tyrant or an encounter card by its id. The id of an encounter card is declared in the card or is the
+    /// file name without the extension.
+    Show {
+        card_id: String,
+    },
+    /// Check every card file and report all the problems with the file paths and line numbers.
+    Validate,
+    /// Show the searched content directories and the resolved paths.
+    Diagnostics,
+    /// Export the battle log of a save as Markdown, CSV or JSON.
+    Export {
+        /// Path of the save file, or its name in the save directory, e.g. "autosave".
+        save: String,
+        #[arg(short, long, value_enum, default_value_t = ExportFormat::Markdown)]
+        format: ExportFormat,
+        /// Output file. Defaults to the save name with the extension of the format.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListTarget {
+    Tyrants,
+    Encounters,
+    Packs,
+    Gearlocs,
+    Baddies,
+}
+
+/// The game to start for `play`. `None` means starting from the boss selection. Without any Gearloc the game starts
+/// with the first Gearloc of the roster.
+pub fn play_setup(library: &CardLibrary, tyrant: Option<String>, seed: Option<u64>, packs: Vec<String>, party: Vec<String>) -> Result<Option<GameSetup>> {
+    match tyrant {
+        None => Ok(None),
+        Some(tyrant_id) => {
+            let tyrant_id = tyrant_id.to_lowercase();
+            if library.tyrant_card(&tyrant_id).is_none() {
+                return Err(eyre!("Tyrant {} doesn't exist. Run `list tyrants` to see the available tyrants.", tyrant_id));
+            }
+            let available_packs = library.packs();
+            if let Some(pack) = packs.iter().find(|pack| !available_packs.contains(pack)) {
+                return Err(eyre!("Content pack {} doesn't exist. Available packs: {}", pack, available_packs.join(", ")));
+            }
+            let mut party: Vec<String> = party.iter().map(|id| id.to_lowercase()).collect();
+            if let Some(id) = party.iter().find(|id| library.gearloc(id).is_none()) {
+                return Err(eyre!("Gearloc {} doesn't exist. Run `list gearlocs` to see the available Gearlocs.", id));
+            }
+            if party.len() > MAX_PARTY_SIZE {
+                return Err(eyre!("A party has at most {} Gearlocs", MAX_PARTY_SIZE));
+            }
+            if party.is_empty() {
+                party.extend(library.gearlocs.first().map(|gearloc| gearloc.id.clone()));
+            }
+            Ok(Some(GameSetup { tyrant_id, seed, packs, party }))
+        }
+    }
+}
+
+/// Run the subcommands that don't need the terminal application. They run before the terminal application and the
+/// file logging are set up. A failed write to the console, e.g. a closed pipe of `tmb list encounters | head -3`, is
+/// returned as the error.
+pub fn run_command(command: Command) -> Result<()> {
+    let mut out = stdout().lock();
+    match command {
+        Command::Play { .. } => unreachable!("play is handled by the terminal application"),
+        Command::List { target } => list(&mut out, target),
+        Command::Show { card_id } => show(&mut out, card_id.as_str()),
+        Command::Validate => validate(&mut out),
+        Command::Diagnostics => diagnostics(&mut out),
+        Command::Export { save, format, output } => {
+            let save_path = resolve_save(save.as_str());
+            let game = SaveGame::read(&save_path).wrap_err_with(|| format!("Failed to read save {:?}", save_path))?;
+            let output = output.unwrap_or_else(|| PathBuf::from(save_path.file_stem().unwrap_or_default()).with_extension(format.extension()));
+            write_battle_logs(game.deck.tyrant_card.name.text(), &game.battle_logs, format, &output)?;
+            writeln!(out, "{}", output.to_string_lossy())?;
+            Ok(())
+        }
+    }
+}
+
+fn list(out: &mut impl Write, target: ListTarget) -> Result<()> {
+    match target {
+        ListTarget::Tyrants => {
+            writeln!(out, "{:<15}{:<15}{:<10}{:<10}source", "id", "name", "progress", "days")?;
+            for file in tyrant_card_files()? {
+                let card = read_card::<TyrantCard>(&file)?;
+                writeln!(out, "{:<15}{:<15}{:<10}{:<10}{}", card.id, card.name, card.min_progress, card.max_days, source(&file))?;
+            }
+        }
+        ListTarget::Encounters => {
+            writeln!(out, "{:<30}{:<10}{:<15}{:<10}title", "id", "type", "pack", "source")?;
+            for (category, file) in encounter_card_files()? {
+                let card = read_card::<EncounterCard>(&file)?;
+                writeln!(out, "{:<30}{:<10}{:<15}{:<10}{}", card.id, category, card.pack, source(&file), card.title)?;
+            }
+        }
+        ListTarget::Packs => {
+            writeln!(out, "{:<15}name", "id")?;
+            for pack in CardLibrary::load().packs() {
+                writeln!(out, "{:<15}{}", pack, pack_name(&pack))?;
+            }
+        }
+        ListTarget::Gearlocs => {
+            writeln!(out, "{:<15}{:<15}{:<10}source", "id", "name", "health")?;
+            for file in list_card_files(GEARLOC_PATH) {
+                let file = file?;
+                let gearloc = read_card::<Gearloc>(&file)?;
+                writeln!(out, "{:<15}{:<15}{:<10}{}", gearloc.id, gearloc.name, gearloc.health, source(&file))?;
+            }
+        }
+        ListTarget::Baddies => {
+            writeln!(out, "{:<20}{:<20}{:<8}{:<6}{:<10}tags", "id", "name", "points", "tier", "source")?;
+            for file in list_card_files(BADDIE_PATH) {
+                let file = file?;
+                let baddie = read_card::<Baddie>(&file)?;
+                writeln!(out, "{:<20}{:<20}{:<8}{:<6}{:<10}{}", baddie.id, baddie.name, baddie.points, baddie.tier, source(&file), baddie.tags.join(", "))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn show(out: &mut impl Write, card_id_to_show: &str) -> Result<()> {
+    if let Some(file) = tyrant_card_files()?.into_iter().find(|file| file.id == card_id_to_show) {
+        let card = read_card::<TyrantCard>(&file)?;
+        writeln!(out, "{} ({})\n", card.name, card.id)?;
+        writeln!(out, "{}\n", card.description)?;
+        writeln!(out, "{}", tr!("最小进度：{}，最大天数：{}，游戏长度：{}", "Min progress: {}, max days: {}, game length: {}", card.min_progress, card.max_days, card.game_length))?;
+        writeln!(out, "{}\n", tr!("Boss战：{}，生物：{}", "Tyrant battle: {}, baddies: {}", card.battle_title, card.creatures))?;
+        for line in card.battle_mechanism.iter().chain(card.tyrant_skills.iter()).chain(card.tyrant_die.iter()) {
+            writeln!(out, "* {}", line)?;
+        }
+        return Ok(());
+    }
+    for (category, file) in encounter_card_files()? {
+        let card = read_card::<EncounterCard>(&file)?;
+        if card.id != card_id_to_show {
+            continue;
+        }
+        writeln!(out, "{} ({}, {})\n", card.title, card.id, category)?;
+        writeln!(out, "{}\n", card.story)?;
+        for (idx, choice) in card.choices.iter().enumerate() {
+            writeln!(out, "{}", tr!("选择{}：{}", "Choice {}: {}", idx + 1, choice.description))?;
+            writeln!(out, "{}", tr!("行动：{}", "Action: {}", choice.action))?;
+            writeln!(out, "{}", tr!("奖励：{}", "Rewards: {}", choice.rewards))?;
+            writeln!(out, "{}\n", tr!("进度：{}", "Progress: {}", card.choice_progress(idx)))?;
+        }
+        writeln!(out, "{}", card.remark)?;
+        return Ok(());
+    }
+    Err(eyre!("Card {} doesn't exist. Run `list tyrants` or `list encounters` to see the card ids.", card_id_to_show))
+}
+
+fn validate(out: &mut impl Write) -> Result<()> {
+    let report = validate_content();
+    for problem in report.problems.iter() {
+        writeln!(out, "{}", problem)?;
+    }
+    if report.problems.is_empty() {
+        writeln!(out, "Checked {} files, no problem found", report.files)?;
+        Ok(())
+    } else {
+        Err(eyre!("Checked {} files, found {} problems", report.files, report.problems.len()))
+    }
+}
+
+fn diagnostics(out: &mut impl Write) -> Result<()> {
+    let resolution = get_content_dir_resolution();
+    writeln!(out, "Content directory search order:")?;
+    for (idx, candidate) in resolution.candidates.iter().enumerate() {
+        writeln!(out, "{:>3}. {:<20}{} {}", idx + 1, candidate.source.description(), candidate.path.to_string_lossy(),
+                 if candidate.found { "(found)" } else { "" })?;
+    }
+    writeln!(out)?;
+    match resolution.content_dir {
+        Some(ref content_dir) => writeln!(out, "Content directory: {}", content_dir.to_string_lossy())?,
+        None => writeln!(out, "Content directory: not found, only the built-in cards are used")?,
+    }
+    writeln!(out, "Save directory:    {}", get_save_dir().to_string_lossy())?;
+    writeln!(out, "History file:      {}", get_history_path().to_string_lossy())?;
+    writeln!(out, "Export directory:  {}", get_export_dir().to_string_lossy())?;
+    writeln!(out, "Settings file:     {}", get_settings_path().to_string_lossy())?;
+    writeln!(out, "Log file:          {}", get_log_path().to_string_lossy())?;
+    Ok(())
+}
+
+fn encounter_card_files() -> Result<Vec<(String, CardFile)>> {
+    let mut files = Vec::new();
+    for (category, file) in list_encounter_card_files() {
+        files.push((category, file?));
+    }
+    Ok(files)
+}
+
+fn tyrant_card_files() -> Result<Vec<CardFile>> {
+    Ok(list_card_files(TYRANT_CARD_GENERAL_PATH).into_iter().collect::<Result<Vec<_>, _>>()?)
+}
+
+fn source(file: &CardFile) -> &'static str {
+    if file.built_in { "built-in" } else { "external" }
+}
@@ -0,0 +1,139 @@
+use std::sync::Arc;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::prelude::*;
+use ratatui::symbols::border;
+use ratatui::widgets::{Block, Borders, Padding, Row, Table};
+use ratatui::widgets::block::{Position, Title};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::info;
+use crate::app::Action;
+use crate::app::Action::Render;
+use crate::component::Component;
+use crate::components::home_page;
+use crate::core::content::get_content_dir_resolution;
+use crate::core::export::get_export_dir;
+use crate::core::game::CardLibrary;
+use crate::core::history::get_history_path;
+use crate::core::i18n::tr;
+use crate::core::save::get_save_dir;
+use crate::core::settings::get_settings_path;
+use crate::utils::get_log_path;
+
+/// Diagnostics of the installation:
+/// - The searched content directories in the search order, and which of them has the `config` directory.
+/// - The resolved content directory, and the save directory, history file, export directory, settings file and log
+///   file under the data and state directories.
+/// - The number of loaded cards and load errors.
+pub const NAME: &str = "DiagnosticsPage";
+
+pub struct DiagnosticsPage {
+    pub name: String,
+    pub action_sender: Option<UnboundedSender<Action>>,
+    library: Arc<CardLibrary>,
+}
+
+impl DiagnosticsPage {
+    pub fn new(library: Arc<CardLibrary>) -> Self {
+        DiagnosticsPage {
+            name: NAME.to_string(),
+            action_sender: None,
+            library,
+        }
+    }
+}
+
+impl Component for DiagnosticsPage {
+    fn register_action_handler(&mut self, sender: UnboundedSender<Action>) -> color_eyre::Result<()> {
+        self.action_sender = Some(sender);
+        Ok(())
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> color_eyre::Result<()> {
+        if key.code == KeyCode::Char('p') {
+            info!("[{}] Went back", self.name);
+            self.action_sender.as_mut().unwrap().send(Render(home_page::NAME.to_string()))?;
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> color_eyre::Result<()> {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Percentage(50),
+                Constraint::Percentage(50),
+            ])
+            .split(area);
+
+        let resolution = get_content_dir_resolution();
+        let used = resolution.candidates.iter().position(|candidate| candidate.found);
+        let mut candidate_rows: Vec<Row> = Vec::new();
+        for (idx, candidate) in resolution.candidates.iter().enumerate() {
+            let selected = used == Some(idx);
+            let r = Row::new(vec![(idx + 1).to_string(),
+                                  candidate.source.description().to_string(),
+                                  candidate.path.to_string_lossy().to_string(),
+                                  if selected { tr!("使用中", "in use") } else if candidate.found { "✅".to_string() } else { "❌".to_string() }]);
+            candidate_rows.push(if selected { r.style(Style::new().yellow()) } else { r });
+        }
+        let candidate_widths = [
+            Constraint::Length(6),
+            Constraint::Length(22),
+            Constraint::Max(100),
+            Constraint::Length(10),
+        ];
+        let candidate_block = Block::default()
+            .title(Title::from(tr!(" 内容目录搜索顺序 ", " Content directory search order ")).alignment(Alignment::Center).position(Position::Top))
+            .borders(Borders::ALL)
+            .padding(Padding::top(1))
+            .border_set(border::THICK);
+        let candidate_table = Table::new(candidate_rows, candidate_widths)
+            .column_spacing(1)
+            .style(Style::new().white())
+            .header(
+                Row::new(vec![tr!("顺序", "Order"), tr!("来源", "Source"), tr!("路径", "Path"), "config".to_string()])
+                    .style(Style::new().bold())
+                    .bottom_margin(1),
+            )
+            .block(candidate_block);
+
+        let library = &self.library;
+        let encounter_cards = library.general_encounter_cards.len() + library.special_encounter_cards.len()
+            + library.day1_encounter_cards.len() + library.day2_encounter_cards.len() + library.day3_encounter_cards.len()
+            + library.tyrant_encounter_cards.values().map(|cards| cards.len()).sum::<usize>();
+        let path_rows = vec![
+            Row::new(vec![tr!("内容目录", "Content directory"), match resolution.content_dir {
+                Some(ref content_dir) => content_dir.to_string_lossy().to_string(),
+                None => tr!("未找到，只使用内置卡牌", "not found, only the built-in cards are used"),
+            }]),
+            Row::new(vec![tr!("存档目录", "Save directory"), get_save_dir().to_string_lossy().to_string()]),
+            Row::new(vec![tr!("历史记录", "History file"), get_history_path().to_string_lossy().to_string()]),
+            Row::new(vec![tr!("导出目录", "Export directory"), get_export_dir().to_string_lossy().to_string()]),
+            Row::new(vec![tr!("设置文件", "Settings file"), get_settings_path().to_string_lossy().to_string()]),
+            Row::new(vec![tr!("日志文件", "Log file"), get_log_path().to_string_lossy().to_string()]),
+            Row::new(vec![tr!("遭遇卡", "Encounter cards"), encounter_cards.to_string()]),
+            Row::new(vec![tr!("Boss卡", "Tyrant cards"), library.tyrant_cards.len().to_string()]),
+            Row::new(vec![tr!("加载错误", "Loading errors"), library.errors.len().to_string()]),
+        ];
+        let path_widths = [
+            Constraint::Length(15),
+            Constraint::Max(100),
+        ];
+        let path_block = Block::default()
+            .title(Title::from(tr!(" 路径 ", " Paths ")).alignment(Alignment::Center).position(Position::Top))
+            .title(Title::from(tr!(" <P> 键回退上一页 || <Q> 键强制退出", " <P> Back || <Q> Quit")).alignment(Alignment::Center).position(Position::Bottom))
+            .borders(Borders::ALL)
+            .padding(Padding::top(1))
+            .border_set(border::THICK);
+        let path_table = Table::new(path_rows, path_widths)
+            .column_spacing(1)
+            .style(Style::new().white())
+            .block(path_block);
+
+        f.render_widget(candidate_table, layout[0]);
+        f.render_widget(path_table, layout[1]);
+        Ok(())
+    }
+}
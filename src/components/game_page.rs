@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::Frame;
 use ratatui::layout::Rect;
@@ -10,9 +11,19 @@ use tracing::info;
 use tui_big_text::{BigText, PixelSize};
 use crate::app::Action;
 use crate::component::Component;
-use crate::components::game_page::ShowPopup::{BreakPopup, ChallengeSuccessfulPopup, NoPopUp};
+use crate::components::game_page::ShowPopup::{BaddieQueuePopup, BossFightPopup, BreakPopup, ChallengeSuccessfulPopup, DicePopup, ExportPopup, NoPopUp};
+use crate::components::{home_page, select_boss_page};
 use crate::components::popup::Popup;
-use crate::core::game::{Choice, EncounterCard, EncounterDeck, SPECIAL_ENCOUNTER_CARDS};
+use crate::core::export::{export_battle_logs, ExportFormat};
+use crate::core::game::{BattleLog, CampaignOutcome, CardLibrary, card_ids, Choice, Effects, EncounterCard, EncounterDeck, GameSetup, pack_name};
+use crate::core::history::{CampaignRecord, new_campaign_id, record_campaign};
+use crate::core::i18n::{LocalizedText, tr};
+use crate::core::baddie::{baddie_points, baddie_queue_seed, BaddieQueue, build_baddie_queue, creature_types};
+use crate::core::dice::{DiceRoll, DiceRoller};
+use crate::core::loot::{LootDecks, LootDraw, LootKind};
+use crate::core::party::{GearlocState, loot_reward, skill_points_reward};
+use crate::core::save::{SaveGame, timestamp};
+use crate::core::settings::{load_settings, save_settings, Settings};
 use crate::core::game::ShuffleStrategy::{FirstTyrantCardTopAndShuffleRest, PickSpecialCardAndShuffle, PutCurrentCardRandom, PutCurrentCardTop, ReplaceTodayEncounterAndShuffleTodayEncounter};
 use crate::utils::centered_rect;
 
@@ -21,56 +32,434 @@ pub const NAME: &str = "GamePage";
 pub struct GamePage {
     pub name: String,
     pub action_sender: Option<UnboundedSender<Action>>,
+    library: Arc<CardLibrary>,
+    campaign_id: String,
     deck: Option<EncounterDeck>,    // Built encounter deck
     days: usize,                    // The current day
     progress: usize,                // The current progress
     today_card: Option<EncounterCard>,
-    should_go_next_day: bool,
     finished_encounter_cards: Vec<EncounterCard>, // The selected choice for the current day
     popup: ShowPopup, // show the popup dialog
     selected_choice: Option<usize>, // The selected choice for the current day
     battle_logs: Vec<BattleLog>,
     menu_select_state: TableState,
-}
-
-#[derive(Debug, Default)]
-struct BattleLog {
-    success: bool,
-    title: String,
-    day: usize,
-    progress: usize,
-    choice: Choice,
+    notice: Option<String>, // The message shown under the encounter card, e.g. the save result
+    outcome: CampaignOutcome,
+    bilingual: bool, // Show the encounter card in both languages
+    party: Vec<GearlocState>, // The Gearlocs in play
+    selected_gearloc: usize, // The Gearloc adjusted by the party panel keys
+    baddie_queue: BaddieQueue, // The Baddie Queue shown in the BQ popup
+    dice: Option<DiceRoller>,
+    pending_rolls: Vec<DiceRoll>, // Today's rolls, recorded into the battle log when the challenge is finished
+    dice_input: String, // The dice typed in the dice popup, e.g. "2d6"
+    loot: Option<LootDecks>,
 }
 
 impl GamePage {
-    pub fn new() -> Self {
+    pub fn new(library: Arc<CardLibrary>, bilingual: bool) -> Self {
         let mut state = TableState::default();
         state.select(Some(0));
         GamePage {
             name: NAME.to_string(),
             action_sender: None,
+            library,
+            campaign_id: String::new(),
             deck: None,
             days: 1,
             progress: 0,
             today_card: None,
-            should_go_next_day: true,
             finished_encounter_cards: Vec::new(),
             popup: NoPopUp,
             selected_choice: None,
             battle_logs: Vec::new(),
             menu_select_state: state,
+            notice: None,
+            outcome: CampaignOutcome::InProgress,
+            bilingual,
+            party: Vec::new(),
+            selected_gearloc: 0,
+            baddie_queue: BaddieQueue::default(),
+            dice: None,
+            pending_rolls: Vec::new(),
+            dice_input: String::new(),
+            loot: None,
+        }
+    }
+
+    /// Throw away the current game. The page goes back to the state before any game is started.
+    fn reset(&mut self) {
+        info!("[{}] Reset the game", self.name);
+        let mut page = GamePage::new(self.library.clone(), self.bilingual);
+        page.action_sender = self.action_sender.take();
+        *self = page;
+    }
+
+    /// The game is over once the last day is reached or no encounter card is left. The boss fight comes next.
+    fn is_game_over(&self) -> bool {
+        match self.deck.as_ref() {
+            None => false,
+            Some(deck) => self.days >= deck.tyrant_card.max_days || deck.encounter_cards.is_empty(),
+        }
+    }
+
+    /// The boss fight is available when the game is over and the progress reaches the requirement of the tyrant.
+    fn can_fight_boss(&self) -> bool {
+        self.is_game_over() && self.progress >= self.deck.as_ref().unwrap().tyrant_card.min_progress
+    }
+
+    fn snapshot(&self) -> SaveGame {
+        SaveGame {
+            saved_at: timestamp(),
+            campaign_id: self.campaign_id.clone(),
+            deck: self.deck.clone().unwrap(),
+            days: self.days,
+            progress: self.progress,
+            today_card: self.today_card.clone(),
+            finished_encounter_cards: self.finished_encounter_cards.clone(),
+            battle_logs: self.battle_logs.clone(),
+            outcome: self.outcome,
+            party: self.party.clone(),
+            dice: self.dice.clone(),
+            pending_rolls: self.pending_rolls.clone(),
+            loot: self.loot.clone(),
+        }
+    }
+
+    /// The Gearlocs of the party of the deck at full health.
+    fn new_party(&self) -> Vec<GearlocState> {
+        let party = self.deck.as_ref().map(|deck| deck.party.as_slice()).unwrap_or_default();
+        party.iter().filter_map(|id| self.library.gearloc(id)).map(GearlocState::new).collect()
+    }
+
+    /// New loot decks for the game of the deck.
+    fn new_loot_decks(&self) -> LootDecks {
+        LootDecks::new(&self.library.loot_cards, &self.library.trove_loot_cards, self.deck.as_ref().unwrap().seed)
+    }
+
+    /// Adjust the selected Gearloc of the party panel. Returns false if the key isn't a party panel key.
+    fn adjust_gearloc(&mut self, key: KeyCode) -> bool {
+        if key == KeyCode::Tab && !self.party.is_empty() {
+            self.selected_gearloc = (self.selected_gearloc + 1) % self.party.len();
+            return true;
+        }
+        let Some(gearloc) = self.party.get_mut(self.selected_gearloc) else { return false };
+        match key {
+            KeyCode::Char('+') => gearloc.heal(),
+            KeyCode::Char('-') => gearloc.damage(),
+            KeyCode::Char(']') => gearloc.raise_max_health(),
+            KeyCode::Char('[') => gearloc.lower_max_health(),
+            KeyCode::Char('u') => gearloc.skill_points = gearloc.skill_points.saturating_sub(1),
+            KeyCode::Char('k') => gearloc.knocked_out = !gearloc.knocked_out,
+            KeyCode::Char('z') => {
+                gearloc.inventory.pop();
+            }
+            _ => return false,
+        }
+        info!("[{}] Adjusted Gearloc {:?}", self.name, gearloc);
+        true
+    }
+
+    /// Record the result of today's challenge with the chosen choice and apply the effects of the choice. The health
+    /// changes whatever the result, the rewards only on success. A choice that draws another encounter skips the break.
+    fn finish_challenge(&mut self, mut log: BattleLog, choice: Choice) {
+        let effects = &choice.effects;
+        let mut notices = Vec::new();
+        if effects.health != 0 && !self.party.is_empty() {
+            // The change is capped by the max health and 0, so the applied change of each Gearloc is kept for the rollback
+            log.health = self.party.iter_mut().map(|gearloc| {
+                let before = gearloc.health as i32;
+                change_health(gearloc, effects.health);
+                gearloc.health as i32 - before
+            }).collect();
+            notices.push(tr!("每个Gearloc生命{:+}", "Each Gearloc health {:+}", effects.health));
+        }
+        if log.success && !self.party.is_empty() {
+            // Skill points given to every Gearloc are applied right away. The loot is drawn by the players.
+            log.skill_points = effects.skill_points.or_else(|| skill_points_reward(&choice.rewards)).unwrap_or_default();
+            if log.skill_points > 0 {
+                for gearloc in self.party.iter_mut() {
+                    gearloc.skill_points += log.skill_points;
+                }
+                notices.push(tr!("每个Gearloc获得{}个技能点", "Each Gearloc gained {} skill points", log.skill_points));
+            }
+            for (kind, amount) in [(LootKind::Loot, effects.loot), (LootKind::TroveLoot, effects.trove_loot)] {
+                let amount = if amount > 0 { amount } else { loot_reward(&choice.rewards, kind).unwrap_or_default() };
+                let drawn = self.draw_loot(kind, amount);
+                log.loot.extend(drawn.iter().cloned());
+                if !drawn.is_empty() {
+                    let names: Vec<String> = drawn.iter().map(|draw| self.loot_name(&draw.card_id)).collect();
+                    notices.push(tr!("抽取了{}", "Drew {}", names.join(tr!("，", ", ").as_str())));
+                }
+                if drawn.len() < amount * self.party.len() {
+                    notices.push(match kind {
+                        LootKind::Loot => tr!("战利品牌堆已空", "The loot deck is empty"),
+                        LootKind::TroveLoot => tr!("宝藏战利品牌堆已空", "The trove loot deck is empty"),
+                    });
+                }
+            }
+        }
+        if !notices.is_empty() {
+            info!("[{}] Applied the effects of the choice: {:?}", self.name, effects);
+            self.notice = Some(notices.join(" | "));
+        }
+        let draw_another_encounter = effects.draw_another_encounter;
+        log.choice = choice;
+        log.rolls = std::mem::take(&mut self.pending_rolls);
+        self.battle_logs.push(log);
+        if draw_another_encounter {
+            self.redraw_encounter();
+        } else {
+            self.popup = BreakPopup(None);
+        }
+        self.check_defeat();
+    }
+
+    /// Draw the loot cards for each Gearloc into the inventories, until the deck is empty.
+    fn draw_loot(&mut self, kind: LootKind, amount: usize) -> Vec<LootDraw> {
+        let mut drawn = Vec::new();
+        let Some(decks) = self.loot.as_mut() else { return drawn };
+        for gearloc in self.party.iter_mut() {
+            for _ in 0..amount {
+                if let Some(draw) = decks.draw(kind, &gearloc.id) {
+                    gearloc.inventory.push(draw.card_id.clone());
+                    drawn.push(draw);
+                }
+            }
         }
+        drawn
+    }
+
+    /// Take the loot drawn by a battle log back from the inventories and put it back on top of the decks.
+    fn rollback_loot(&mut self, draws: &[LootDraw]) {
+        for draw in draws.iter() {
+            if let Some(gearloc) = self.party.iter_mut().find(|gearloc| gearloc.id == draw.gearloc) {
+                if let Some(idx) = gearloc.inventory.iter().rposition(|card_id| *card_id == draw.card_id) {
+                    gearloc.inventory.remove(idx);
+                }
+            }
+        }
+        if let Some(decks) = self.loot.as_mut() {
+            decks.rollback(draws);
+        }
+    }
+
+    fn loot_name(&self, card_id: &str) -> String {
+        self.library.loot_card(card_id).map(|card| card.name.to_string()).unwrap_or_else(|| card_id.to_string())
+    }
+
+    /// Shuffle today's encounter card back into the deck and draw another one for today. The day doesn't change, so
+    /// the battle log of today's card is marked to keep the logs of the day in order for the rollback. Without general
+    /// encounter cards left to draw, today's encounter is kept and the break popup tells so.
+    fn redraw_encounter(&mut self) {
+        if self.deck.as_ref().unwrap().pool.general_encounter_cards.is_empty() {
+            info!("[{}] No general encounter card left to draw for day {}", self.name, self.days);
+            self.popup = BreakPopup(Some(tr!("通用遭遇牌已用完，无法抽取另一个遭遇", "No general encounter card left, can't draw another encounter")));
+            return;
+        }
+        info!("[{}] Drawing another encounter for day {}", self.name, self.days);
+        let deck = self.deck.as_mut().unwrap();
+        deck.shuffle(ReplaceTodayEncounterAndShuffleTodayEncounter, self.today_card.take());
+        if let Some(log) = self.battle_logs.last_mut() {
+            log.redrawn = true;
+            log.replacement = deck.encounter_cards[0].id.clone();
+        }
+        self.draw_today_card();
+        self.popup = NoPopUp;
+    }
+
+    /// Put today's encounter card away and draw the card of the day from the deck. Once the game is over no card is
+    /// drawn, the boss fight comes next.
+    fn draw_today_card(&mut self) {
+        if let Some(today_card) = self.today_card.take() {
+            self.finished_encounter_cards.push(today_card);
+        }
+        if self.is_game_over() {
+            return;
+        }
+        let card = self.deck.as_mut().unwrap().encounter_cards.remove(0);
+        self.today_card = Some(card);
+        info!("[{}] Went to day {}. Finished encounter deck: {:?}.", self.name, self.days, card_ids(&self.finished_encounter_cards));
+        self.autosave();
+    }
+
+    /// The baddie points of the BQ for a choice of today's encounter: the day times the party size plus the extra
+    /// points of the choice.
+    fn choice_baddie_points(&self, choice: usize) -> usize {
+        let base = baddie_points(self.days, self.party.len()) as i32;
+        let extra = self.today_card.as_ref()
+            .and_then(|card| card.choices.get(choice))
+            .map(|choice| choice.effects.extra_baddie_points(self.party.len()))
+            .unwrap_or_default();
+        (base + extra).max(0) as usize
+    }
+
+    /// Draw the Baddie Queue again for the choice and the points of the BQ popup.
+    fn draw_baddie_queue(&mut self) {
+        let deck = self.deck.as_ref().unwrap();
+        let queue = &mut self.baddie_queue;
+        let seed = baddie_queue_seed(deck.seed, self.days, queue.choice, queue.rerolls);
+        queue.baddies = build_baddie_queue(&self.library.baddies, queue.points, &creature_types(&deck.tyrant_card.creatures), self.days, seed);
+        info!("[{}] Drew the Baddie Queue for choice {} with {} points: {:?}", self.name, queue.choice + 1, queue.points,
+            queue.baddies.iter().map(|baddie| baddie.id.as_str()).collect::<Vec<&str>>());
+    }
+
+    /// Switch the encounter card between one language and both languages. The choice is kept in the settings.
+    fn toggle_bilingual(&mut self) {
+        self.bilingual = !self.bilingual;
+        info!("[{}] Bilingual display: {}", self.name, self.bilingual);
+        let settings = Settings { bilingual: self.bilingual, ..load_settings().unwrap_or_default() };
+        self.notice = match save_settings(&settings) {
+            Ok(()) if self.bilingual => Some(tr!("已切换为双语显示", "Showing both languages")),
+            Ok(()) => Some(tr!("已切换为单语显示", "Showing one language")),
+            Err(e) => Some(tr!("保存设置失败：{}", "Failed to save the settings: {}", e)),
+        };
+    }
+
+    /// Save the current campaign into a new save file. The result is shown as a notice on the page.
+    fn save(&mut self) {
+        let save = self.snapshot();
+        self.notice = match save.write() {
+            Ok(path) => Some(tr!("已保存至 {}", "Saved to {}", path.to_string_lossy())),
+            Err(e) => Some(tr!("保存失败：{}", "Failed to save: {}", e)),
+        };
+    }
+
+    /// Export the battle log into the export directory. The result is shown as a notice on the page.
+    fn export(&mut self, format: ExportFormat) {
+        let tyrant_name = self.deck.as_ref().unwrap().tyrant_card.name.text();
+        self.notice = match export_battle_logs(tyrant_name, &self.battle_logs, format) {
+            Ok(path) => Some(tr!("已导出至 {}", "Exported to {}", path.to_string_lossy())),
+            Err(e) => Some(tr!("导出失败：{}", "Failed to export: {}", e)),
+        };
+    }
+
+    /// Set the final result of the campaign and record it into the campaign history.
+    fn finish_campaign(&mut self, outcome: CampaignOutcome) {
+        self.outcome = outcome;
+        let deck = self.deck.as_ref().unwrap();
+        let record = CampaignRecord {
+            campaign_id: self.campaign_id.clone(),
+            finished_at: timestamp(),
+            tyrant_id: deck.tyrant_card.id.clone(),
+            tyrant_name: deck.tyrant_card.name.clone(),
+            seed: deck.seed,
+            days: self.days,
+            max_days: deck.tyrant_card.max_days,
+            progress: self.progress,
+            min_progress: deck.tyrant_card.min_progress,
+            outcome,
+            battle_logs: self.battle_logs.clone(),
+        };
+        if let Err(e) = record_campaign(record) {
+            info!("[{}] Failed to record the campaign: {}", self.name, e);
+        }
+        self.autosave();
+    }
+
+    /// Overwrite the autosave with the current campaign. Failures are only logged, the game goes on.
+    fn autosave(&self) {
+        if let Err(e) = self.snapshot().write_autosave() {
+            info!("[{}] Autosave failed: {}", self.name, e);
+        }
+    }
+
+    /// Replace the whole game state with the one in the save.
+    fn restore(&mut self, mut save: SaveGame) {
+        self.reset();
+        let deck = &mut save.deck;
+        let pool = &mut deck.pool;
+        self.library.fill_card_ids(deck.encounter_cards.iter_mut()
+            .chain(save.today_card.iter_mut())
+            .chain(save.finished_encounter_cards.iter_mut())
+            .chain(pool.general_encounter_cards.iter_mut())
+            .chain(pool.special_encounter_cards.iter_mut())
+            .chain(pool.day1_encounter_cards.iter_mut())
+            .chain(pool.day2_encounter_cards.iter_mut())
+            .chain(pool.day3_encounter_cards.iter_mut())
+            .chain(pool.tyrant_encounter_cards.values_mut().flatten()));
+        self.campaign_id = save.campaign_id;
+        self.deck = Some(save.deck);
+        self.days = save.days;
+        self.progress = save.progress;
+        self.today_card = save.today_card;
+        self.finished_encounter_cards = save.finished_encounter_cards;
+        self.popup = NoPopUp;
+        self.selected_choice = None;
+        self.battle_logs = save.battle_logs;
+        self.outcome = save.outcome;
+        self.party = if save.party.is_empty() { self.new_party() } else { save.party };
+        self.dice = Some(save.dice.unwrap_or_else(|| DiceRoller::new(self.deck.as_ref().unwrap().seed)));
+        self.pending_rolls = save.pending_rolls;
+        self.loot = Some(save.loot.unwrap_or_else(|| self.new_loot_decks()));
+        self.menu_select_state.select(Some(0));
+        self.notice = Some(tr!("已读取 {} 的存档", "Loaded the save of {}", save.saved_at));
+        if self.is_game_over() {
+            // The last card of a finished game is put away
+            self.draw_today_card();
+        }
+        self.check_defeat();
+    }
+
+    /// Lose the campaign once the days or the encounter cards run out without the progress to challenge the tyrant.
+    fn check_defeat(&mut self) {
+        if self.outcome == CampaignOutcome::InProgress && self.is_game_over() && !self.can_fight_boss() {
+            info!("[{}] Not enough progress to challenge the boss. Campaign lost.", self.name);
+            self.finish_campaign(CampaignOutcome::Defeat);
+        }
+    }
+
+    fn gearloc_name(&self, id: &str) -> String {
+        self.library.gearloc(id).map(|gearloc| gearloc.name.to_string()).unwrap_or_else(|| id.to_string())
+    }
+
+    /// A roll for display, e.g. "2d6: 3 + 5 = 8" or "Boomer health check: 4 ✅".
+    fn describe_roll(&self, roll: &DiceRoll) -> String {
+        match (&roll.gearloc, roll.passed) {
+            (Some(gearloc), Some(passed)) => tr!("{}健康检查：{} {}", "{} health check: {} {}", self.gearloc_name(gearloc), roll.total(), if passed { "✅" } else { "❌" }),
+            _ if roll.results.len() == 1 => format!("{}: {}", roll.expression, roll.total()),
+            _ => format!("{}: {} = {}", roll.expression, roll.results.iter().map(|result| result.to_string()).collect::<Vec<String>>().join(" + "), roll.total()),
+        }
+    }
+
+    /// The party panel next to the encounter card: health, unspent skill points and knocked out state of each Gearloc.
+    fn build_party_panel(&self) -> Paragraph<'_> {
+        let mut lines: Vec<Line> = Vec::new();
+        for (idx, gearloc) in self.party.iter().enumerate() {
+            let name = self.gearloc_name(&gearloc.id);
+            let style = if idx == self.selected_gearloc { Style::new().yellow() } else { Style::new() };
+            let marker = if idx == self.selected_gearloc { ">> " } else { "   " };
+            lines.push(Line::styled(format!("{}{}", marker, name), style.bold()));
+            let status = if gearloc.knocked_out { tr!(" 已击倒", " knocked out") } else { String::new() };
+            lines.push(Line::styled(tr!("   生命 {}/{} 技能点 {}{}", "   HP {}/{} SP {}{}", gearloc.health, gearloc.max_health, gearloc.skill_points, status), style));
+            for card_id in gearloc.inventory.iter() {
+                lines.push(Line::styled(format!("   * {}", self.loot_name(card_id)), style));
+            }
+            lines.push(Line::raw(""));
+        }
+        lines.push(Line::styled(tr!("<Tab> 选择 <+/-> 生命", "<Tab> Select <+/-> Health"), Style::new().dark_gray()));
+        lines.push(Line::styled(tr!("<[/]> 生命上限 <U> 使用技能点", "<[/]> Max health <U> Spend SP"), Style::new().dark_gray()));
+        lines.push(Line::styled(tr!("<K> 击倒/站起 <Z> 弃掉最后的战利品", "<K> Knocked out <Z> Discard loot"), Style::new().dark_gray()));
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::new().borders(Borders::ALL)
+                .border_set(border::THICK)
+                .title(Title::from(tr!(" 队伍 ", " Party ")).alignment(Alignment::Center)))
     }
 }
 
 impl Component for GamePage {
+    fn register_action_handler(&mut self, sender: UnboundedSender<Action>) -> color_eyre::Result<()> {
+        self.action_sender = Some(sender);
+        Ok(())
+    }
+
     fn handle_key_events(&mut self, key: KeyEvent) -> color_eyre::Result<()> {
         match self.popup {
             NoPopUp => {
                 let mut idx = self.menu_select_state.selected().unwrap();
                 if key.code == KeyCode::Char('1') {
                     self.popup = if let Some(ref card) = self.today_card {
-                        if card.choices.len() >= 1 {
+                        if !card.choices.is_empty() {
                             self.selected_choice = Some(0);
                             info!("[{}] Selected choice 1", self.name);
                             ChallengeSuccessfulPopup
@@ -108,58 +497,132 @@ impl Component for GamePage {
                     }
                 }
                 if key.code == KeyCode::Up {
-                    if idx > 0 {
-                        idx -= 1;
-                    }
+                    idx = idx.saturating_sub(1);
                     self.menu_select_state.select(Some(idx));
                 }
                 if key.code == KeyCode::Down {
-                    if idx < self.battle_logs.len() - 1 {
+                    if idx + 1 < self.battle_logs.len() {
                         idx += 1;
                     }
                     self.menu_select_state.select(Some(idx));
                 }
-                if key.code == KeyCode::Enter {
-                    let day = idx + 1;
+                if key.code == KeyCode::Char('s') {
+                    self.save();
+                }
+                if key.code == KeyCode::Char('x') {
+                    self.popup = ExportPopup;
+                }
+                if key.code == KeyCode::Char('l') {
+                    self.toggle_bilingual();
+                }
+                if key.code == KeyCode::Char('g') && self.today_card.as_ref().is_some_and(|card| !card.choices.is_empty()) {
+                    self.baddie_queue = BaddieQueue {
+                        points: self.choice_baddie_points(0),
+                        ..Default::default()
+                    };
+                    self.draw_baddie_queue();
+                    self.popup = BaddieQueuePopup;
+                }
+                if key.code == KeyCode::Char('d') {
+                    self.popup = DicePopup;
+                }
+                if self.adjust_gearloc(key.code) {
+                    self.autosave();
+                }
+                if key.code == KeyCode::Char('b') && self.can_fight_boss() && self.outcome == CampaignOutcome::InProgress {
+                    info!("[{}] Recording the boss fight", self.name);
+                    self.popup = BossFightPopup;
+                }
+                if self.is_game_over() {
+                    let sender = self.action_sender.clone().unwrap();
+                    if key.code == KeyCode::Char('h') {
+                        info!("[{}] Selected back to home page", self.name);
+                        sender.send(Action::Reset(NAME.to_string()))?;
+                        sender.send(Action::Render(home_page::NAME.to_string()))?;
+                    }
+                    if key.code == KeyCode::Char('r') {
+                        let deck = self.deck.as_ref().unwrap();
+                        let setup = GameSetup {
+                            tyrant_id: deck.tyrant_card.id.clone(),
+                            seed: None,
+                            packs: deck.packs.clone(),
+                            party: deck.party.clone(),
+                        };
+                        info!("[{}] Selected restart with {:?}", self.name, setup);
+                        sender.send(Action::NewGame(NAME.to_string(), setup))?;
+                    }
+                    if key.code == KeyCode::Char('t') {
+                        info!("[{}] Selected another tyrant", self.name);
+                        sender.send(Action::Reset(NAME.to_string()))?;
+                        sender.send(Action::Render(select_boss_page::NAME.to_string()))?;
+                    }
+                }
+                if key.code == KeyCode::Enter && self.battle_logs.get(idx).is_some_and(|log| log.tyrant) {
+                    // Rolling back the boss fight only forgets its result. The encounter deck stays untouched.
+                    let log = self.battle_logs.remove(idx);
+                    info!("[{}] Removed boss fight log: {}", self.name, log.card_id);
+                    self.outcome = CampaignOutcome::InProgress;
+                    self.menu_select_state.select(Some(idx.saturating_sub(1)));
+                    self.autosave();
+                } else if key.code == KeyCode::Enter && !self.battle_logs.is_empty() {
+                    // A day has more than one battle log if its encounter was redrawn
+                    let day = self.battle_logs[idx].day;
                     info!("[{}] Selected rollback to day {}", self.name, day);
-                    self.finished_encounter_cards.push(self.today_card.take().unwrap());
+                    if let Some(today_card) = self.today_card.take() {
+                        self.finished_encounter_cards.push(today_card);
+                    }
                     self.deck.as_mut().unwrap().rollback(&mut self.finished_encounter_cards, day);
-                    let mut i = self.battle_logs.len();
-                    while i >= day {
+                    while self.battle_logs.last().is_some_and(|log| log.day >= day) {
                         let log = self.battle_logs.pop().unwrap();
-                        info!("[{}] Removed battle log {:?}", self.name, log);
-                        i -= 1;
+                        info!("[{}] Removed battle log of day {}: {}", self.name, log.day, log.card_id);
+                        if log.redrawn {
+                            self.deck.as_mut().unwrap().rollback_redraw(&log.card_id, &log.replacement);
+                        }
                         self.progress -= log.progress;
+                        for gearloc in self.party.iter_mut() {
+                            gearloc.skill_points = gearloc.skill_points.saturating_sub(log.skill_points);
+                        }
+                        for (gearloc, health) in self.party.iter_mut().zip(&log.health) {
+                            change_health(gearloc, -health);
+                        }
+                        self.rollback_loot(&log.loot);
                     }
                     self.days = day;
+                    self.pending_rolls.clear();
+                    self.outcome = CampaignOutcome::InProgress;
                     self.today_card = Some(self.deck.as_mut().unwrap().encounter_cards.remove(0));
                     info!("[{}] Finished rollback to day {}", self.name, day);
+                    self.autosave();
                 }
             }
             ChallengeSuccessfulPopup => {
                 if key.code == KeyCode::Char('y') {
-                    self.popup = BreakPopup(None);
                     let today_card = self.today_card.as_ref().unwrap();
-                    let today_progress = today_card.progress[self.selected_choice.unwrap()];
+                    let today_progress = today_card.choice_progress(self.selected_choice.unwrap());
                     self.progress += today_progress;
-                    info!("[{}] Choice {} challenge successful", self.name, self.selected_choice.unwrap() + 1);
-                    self.battle_logs.push(BattleLog {
+                    info!("[{}] Choice {} of {} challenge successful", self.name, self.selected_choice.unwrap() + 1, today_card.id);
+                    let choice = today_card.choices[self.selected_choice.unwrap()].clone();
+                    let log = BattleLog {
+                        card_id: today_card.id.clone(),
                         success: true,
                         title: today_card.title.clone(),
                         day: self.days,
                         progress: today_progress,
-                        choice: today_card.choices[self.selected_choice.unwrap()].clone(),
-                    })
+                        ..Default::default()
+                    };
+                    self.finish_challenge(log, choice);
                 }
                 if key.code == KeyCode::Char('n') {
-                    self.popup = BreakPopup(None);
                     let today_card = self.today_card.as_ref().unwrap();
-                    info!("[{}] Choice {} challenge failed", self.name, self.selected_choice.unwrap());
-                    let mut log = BattleLog::default();
-                    log.title = today_card.title.clone();
-                    log.day = self.days;
-                    log.choice = today_card.choices[self.selected_choice.unwrap()].clone();
-                    self.battle_logs.push(log);
+                    info!("[{}] Choice {} of {} challenge failed", self.name, self.selected_choice.unwrap() + 1, today_card.id);
+                    let choice = today_card.choices[self.selected_choice.unwrap()].clone();
+                    let log = BattleLog {
+                        card_id: today_card.id.clone(),
+                        title: today_card.title.clone(),
+                        day: self.days,
+                        ..Default::default()
+                    };
+                    self.finish_challenge(log, choice);
                 }
                 if key.code == KeyCode::Char('p') {
                     self.popup = NoPopUp;
@@ -167,6 +630,113 @@ impl Component for GamePage {
                     info!("[{}] Went back", self.name);
                 }
             }
+            BossFightPopup => {
+                let mut outcome = None;
+                if key.code == KeyCode::Char('y') {
+                    outcome = Some(CampaignOutcome::Victory);
+                }
+                if key.code == KeyCode::Char('n') {
+                    outcome = Some(CampaignOutcome::Defeat);
+                }
+                if key.code == KeyCode::Char('p') {
+                    self.popup = NoPopUp;
+                }
+                if let Some(outcome) = outcome {
+                    let tyrant_card = &self.deck.as_ref().unwrap().tyrant_card;
+                    info!("[{}] Boss fight finished: {:?}", self.name, outcome);
+                    self.battle_logs.push(BattleLog {
+                        card_id: tyrant_card.id.clone(),
+                        success: outcome == CampaignOutcome::Victory,
+                        title: tyrant_card.battle_title.clone(),
+                        day: self.days,
+                        progress: 0,
+                        choice: Choice {
+                            description: LocalizedText::new("Boss战", "Tyrant battle"),
+                            action: tyrant_card.name.clone(),
+                            rewards: LocalizedText::default(),
+                            effects: Effects::default(),
+                        },
+                        tyrant: true,
+                        skill_points: 0,
+                        health: Vec::new(),
+                        rolls: std::mem::take(&mut self.pending_rolls),
+                        loot: Vec::new(),
+                        redrawn: false,
+                        replacement: String::new(),
+                    });
+                    self.popup = NoPopUp;
+                    self.finish_campaign(outcome);
+                }
+            }
+            ExportPopup => {
+                let format = match key.code {
+                    KeyCode::Char('m') => Some(ExportFormat::Markdown),
+                    KeyCode::Char('c') => Some(ExportFormat::Csv),
+                    KeyCode::Char('j') => Some(ExportFormat::Json),
+                    _ => None,
+                };
+                if let Some(format) = format {
+                    info!("[{}] Exporting the battle log as {:?}", self.name, format);
+                    self.export(format);
+                    self.popup = NoPopUp;
+                }
+                if key.code == KeyCode::Char('p') {
+                    self.popup = NoPopUp;
+                }
+            }
+            BaddieQueuePopup => {
+                let choices = self.today_card.as_ref().map(|card| card.choices.len()).unwrap_or_default();
+                let mut redraw = true;
+                if let KeyCode::Char(c @ '1'..='3') = key.code {
+                    if (c as usize - '1' as usize) < choices {
+                        self.baddie_queue.choice = c as usize - '1' as usize;
+                        self.baddie_queue.points = self.choice_baddie_points(self.baddie_queue.choice);
+                    }
+                }
+                let queue = &mut self.baddie_queue;
+                match key.code {
+                    KeyCode::Char('1'..='3') => {}
+                    KeyCode::Char('+') => queue.points += 1,
+                    KeyCode::Char('-') => queue.points = queue.points.saturating_sub(1),
+                    KeyCode::Char('r') => queue.rerolls += 1,
+                    KeyCode::Char('p') => {
+                        self.popup = NoPopUp;
+                        redraw = false;
+                    }
+                    _ => redraw = false,
+                }
+                if redraw {
+                    self.draw_baddie_queue();
+                }
+            }
+            DicePopup => {
+                match key.code {
+                    KeyCode::Char(c) if (c.is_ascii_digit() || c == 'd') && self.dice_input.len() < 8 => self.dice_input.push(c),
+                    KeyCode::Backspace => {
+                        self.dice_input.pop();
+                    }
+                    KeyCode::Tab if !self.party.is_empty() => self.selected_gearloc = (self.selected_gearloc + 1) % self.party.len(),
+                    KeyCode::Enter => {
+                        let expression = if self.dice_input.is_empty() { "d6" } else { self.dice_input.as_str() };
+                        match self.dice.as_mut().unwrap().roll(expression) {
+                            Some(roll) => {
+                                self.pending_rolls.push(roll);
+                                self.autosave();
+                            }
+                            None => self.notice = Some(tr!("无效的骰子：{}", "Invalid dice: {}", expression)),
+                        }
+                    }
+                    KeyCode::Char('c') => {
+                        if let Some(gearloc) = self.party.get(self.selected_gearloc) {
+                            let roll = self.dice.as_mut().unwrap().health_check(&gearloc.id, gearloc.health);
+                            self.pending_rolls.push(roll);
+                            self.autosave();
+                        }
+                    }
+                    KeyCode::Char('p') => self.popup = NoPopUp,
+                    _ => {}
+                }
+            }
             BreakPopup(_) => {
                 let mut next_day = false;
                 if key.code == KeyCode::Char('a') {
@@ -185,36 +755,37 @@ impl Component for GamePage {
                     self.deck.as_mut().unwrap().shuffle(PutCurrentCardRandom, self.today_card.take());
                 }
                 if key.code == KeyCode::Char('e') {
-                    self.days -= 1;
-                    next_day = true;
-                    self.deck.as_mut().unwrap().shuffle(ReplaceTodayEncounterAndShuffleTodayEncounter, self.today_card.take());
+                    self.redraw_encounter();
                 }
                 if key.code == KeyCode::Char('o') {
                     info!("[{}] Selected boss challenge", self.name);
                     let min_required = self.deck.as_ref().unwrap().tyrant_card.min_progress;
                     if self.progress < min_required {
-                        self.popup = BreakPopup(Some(format!("无法挑战，最小进度要求: {}, 当前：{}", min_required, self.progress)));
+                        self.popup = BreakPopup(Some(tr!("无法挑战，最小进度要求: {}, 当前：{}", "Can't challenge the tyrant. Progress required: {}, current: {}", min_required, self.progress)));
                     } else {
                         self.popup = NoPopUp;
                         self.deck.as_mut().unwrap().encounter_cards.clear();
                         self.days += 1;
+                        self.draw_today_card();
                     }
                 }
-                let mut special_encounters = SPECIAL_ENCOUNTER_CARDS.lock().unwrap();
-                for (i, _) in special_encounters.iter().enumerate() {
+                let deck = self.deck.as_mut().unwrap();
+                for i in 0..deck.pool.special_encounter_cards.len() {
                     let code_point = 'f' as u32;
                     let new_code_point = code_point + i as u32;
                     let new_char = std::char::from_u32(new_code_point).unwrap();
                     if key.code == KeyCode::Char(new_char) {
                         next_day = true;
-                        self.deck.as_mut().unwrap().shuffle(PickSpecialCardAndShuffle, Some(special_encounters.remove(i)));
+                        let special_card = deck.pool.special_encounter_cards.remove(i);
+                        deck.shuffle(PickSpecialCardAndShuffle, Some(special_card));
                         break;
                     }
                 }
                 if next_day {
                     self.popup = NoPopUp;
-                    self.should_go_next_day = true;
                     self.days += 1;
+                    self.draw_today_card();
+                    self.check_defeat();
                 }
             }
         }
@@ -222,15 +793,52 @@ impl Component for GamePage {
     }
     fn update(&mut self, action: Action) -> color_eyre::Result<()> {
         match action {
-            Action::Update(_, tyrant_name) => {
-                let deck = EncounterDeck::new(tyrant_name.as_str());
-                self.deck = Some(deck);
+            Action::NewGame(_, setup) => {
+                self.reset();
+                match EncounterDeck::new(&self.library, &setup) {
+                    Ok(deck) => {
+                        self.campaign_id = new_campaign_id(&setup.tyrant_id);
+                        self.dice = Some(DiceRoller::new(deck.seed));
+                        self.deck = Some(deck);
+                        self.party = self.new_party();
+                        self.loot = Some(self.new_loot_decks());
+                        self.draw_today_card();
+                    }
+                    Err(e) => {
+                        info!("[{}] Failed to build the encounter deck for {:?}: {}", self.name, setup, e);
+                        let sender = self.action_sender.as_mut().unwrap();
+                        sender.send(Action::Update(home_page::NAME.to_string(), tr!("无法开始游戏：{}", "Failed to start the game: {}", e)))?;
+                        sender.send(Action::Render(home_page::NAME.to_string()))?;
+                    }
+                }
+            }
+            Action::Reset(_) => {
+                self.reset();
+            }
+            Action::Load(_, save_path) => {
+                match SaveGame::read(&save_path) {
+                    Ok(save) => {
+                        info!("[{}] Restoring the campaign from {:?}", self.name, save_path);
+                        self.restore(save);
+                        self.action_sender.as_mut().unwrap().send(Action::Render(NAME.to_string()))?;
+                    }
+                    Err(e) => {
+                        info!("[{}] Failed to restore the campaign from {:?}: {}", self.name, save_path, e);
+                        let sender = self.action_sender.as_mut().unwrap();
+                        sender.send(Action::Update(home_page::NAME.to_string(), tr!("无法读取存档 {}：{}", "Failed to load the save {}: {}", save_path.display(), e)))?;
+                        sender.send(Action::Render(home_page::NAME.to_string()))?;
+                    }
+                }
             }
             _ => {}
         }
         Ok(())
     }
     fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> color_eyre::Result<()> {
+        if self.deck.is_none() {
+            // The game was reset and the page is about to be switched.
+            return Ok(());
+        }
         let layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints(vec![
@@ -240,6 +848,13 @@ impl Component for GamePage {
             ])
             .split(area);
 
+        let header_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(layout[0]);
         let days = String::from("Days: ") + self.days.to_string().as_str();
         let progress = String::from("Progress: ") + self.progress.to_string().as_str();
         // days banner
@@ -253,95 +868,169 @@ impl Component for GamePage {
             .alignment(Alignment::Center)
             .build()?;
 
-        let table = build_battle_log_menu(&self.battle_logs);
+        let rolls = self.battle_logs.iter()
+            .map(|log| log.rolls.iter().map(|roll| self.describe_roll(roll)).collect::<Vec<String>>().join(", "))
+            .collect();
+        let table = build_battle_log_menu(&self.battle_logs, rolls);
 
-        frame.render_widget(days_banner, layout[0]);
+        let deck = self.deck.as_ref().unwrap();
+        let packs = if deck.packs.is_empty() {
+            tr!("全部", "all")
+        } else {
+            deck.packs.iter().map(|pack| pack_name(pack)).collect::<Vec<String>>().join(", ")
+        };
+        let party = self.library.party_names(&deck.party).join(", ");
+        let seed = Paragraph::new(tr!("Seed: {} | 扩展: {} | 队伍: {}", "Seed: {} | Packs: {} | Party: {}", deck.seed, packs, party))
+            .style(Style::new().dark_gray())
+            .alignment(Alignment::Center);
+
+        frame.render_widget(days_banner, header_layout[0]);
+        frame.render_widget(seed, header_layout[1]);
         frame.render_stateful_widget(table, layout[2], &mut self.menu_select_state);
 
-        let deck = self.deck.as_mut().unwrap();
         let content;
-        if self.days < deck.tyrant_card.max_days && !deck.encounter_cards.is_empty() {
-            if self.should_go_next_day {
-
-                match self.today_card.take() {
-                    None => {}
-                    Some(today_card) => { self.finished_encounter_cards.push(today_card) }
-                }
-                info!("[{}] Went to day {}. Finished encounter deck: {:?}.", self.name, self.days, self.finished_encounter_cards);
-                let card = deck.encounter_cards.remove(0);
-                self.today_card = Some(card);
-                self.should_go_next_day = false;
-            }
+        if !self.is_game_over() {
             match self.today_card.as_ref() {
                 None => {
                     let card = self.finished_encounter_cards.last().unwrap();
-                    content = build_encounter_content(card, self.days);
+                    content = build_encounter_content(card, self.days, self.bilingual);
                 }
                 Some(card) => {
-                    content = build_encounter_content(card, self.days);
+                    content = build_encounter_content(card, self.days, self.bilingual);
                 }
             }
         } else {
-            match self.today_card.take() {
-                None => {}
-                Some(today_card) => { self.finished_encounter_cards.push(today_card) }
-            }
-
             let card = &self.deck.as_ref().unwrap().tyrant_card;
             if self.progress >= card.min_progress {
-                let mut intro = "Boss战：".to_string() + card.battle_title.as_str();
-                intro = intro + "\n------------------\n";
-                intro = intro + card.description.as_str();
-                intro = intro + "\n------------------\n战斗机制：\n";
+                let mut intro = tr!("Boss战：{}", "Tyrant battle: {}", card.battle_title);
+                intro += "\n------------------\n";
+                intro += card.description.text();
+                intro += tr!("\n------------------\n战斗机制：\n", "\n------------------\nBattle mechanism:\n").as_str();
                 for m in &card.battle_mechanism {
-                    intro = intro + " *" + m.as_str() + "\n";
+                    intro = intro + " *" + m.text() + "\n";
                 }
-                intro = intro + "\n------------------\nBoss技能：\n";
+                intro += tr!("\n------------------\nBoss技能：\n", "\n------------------\nTyrant skills:\n").as_str();
                 for s in &card.tyrant_skills {
-                    intro = intro + " *" + s.as_str() + "\n";
+                    intro = intro + " *" + s.text() + "\n";
                 }
-                intro = intro + "\n------------------\nBoss骰子：\n";
+                intro += tr!("\n------------------\nBoss骰子：\n", "\n------------------\nTyrant die:\n").as_str();
                 for d in &card.tyrant_die {
-                    intro = intro + " *" + d.as_str() + "\n";
+                    intro = intro + " *" + d.text() + "\n";
                 }
+                intro += "\n------------------\n";
+                intro += build_summary(&self.battle_logs, self.progress, self.outcome).as_str();
                 content = intro;
             } else {
-                content = format!("游戏结束。进度点没有达到要求 {}", card.min_progress);
+                content = tr!("游戏结束。进度点没有达到要求 {}\n\n{}", "Game over. The progress didn't reach {}\n\n{}", card.min_progress, build_summary(&self.battle_logs, self.progress, self.outcome));
             }
         }
 
-        let instruction = Title::from(" <1> 第一个选择 || <2> 第二个选择 || <3> 第三个选择 || <Q> 强制退出 ".bold());
+        let instruction = if self.can_fight_boss() && self.outcome == CampaignOutcome::InProgress {
+            Title::from(tr!(" <B> 记录Boss战结果 || <H> 返回主菜单 || <R> 重新挑战当前Boss || <T> 选择其他Boss || <S> 保存 || <X> 导出 || <L> 双语 || <Q> 强制退出 ",
+                            " <B> Record the tyrant battle || <H> Main menu || <R> Retry this tyrant || <T> Another tyrant || <S> Save || <X> Export || <L> Bilingual || <Q> Quit ").bold())
+        } else if self.is_game_over() {
+            Title::from(tr!(" <H> 返回主菜单 || <R> 重新挑战当前Boss || <T> 选择其他Boss || <S> 保存 || <X> 导出 || <L> 双语 || <Q> 强制退出 ",
+                            " <H> Main menu || <R> Retry this tyrant || <T> Another tyrant || <S> Save || <X> Export || <L> Bilingual || <Q> Quit ").bold())
+        } else {
+            Title::from(tr!(" <1> 第一个选择 || <2> 第二个选择 || <3> 第三个选择 || <G> 反派队列 || <D> 骰子 || <S> 保存 || <X> 导出 || <L> 双语 || <Q> 强制退出 ",
+                            " <1> First choice || <2> Second choice || <3> Third choice || <G> Baddie Queue || <D> Dice || <S> Save || <X> Export || <L> Bilingual || <Q> Quit ").bold())
+        };
+        let mut block = Block::new().borders(Borders::ALL)
+            .border_set(border::THICK)
+            .title(instruction.alignment(Alignment::Center).position(Position::Top));
+        if let Some(notice) = &self.notice {
+            block = block.title(Title::from(format!(" {} ", notice)).alignment(Alignment::Center).position(Position::Bottom));
+        }
+        let encounter_area = if self.party.is_empty() {
+            layout[1]
+        } else {
+            let body_layout = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Min(0), Constraint::Length(34)])
+                .split(layout[1]);
+            frame.render_widget(self.build_party_panel(), body_layout[1]);
+            body_layout[0]
+        };
         frame.render_widget(
             Paragraph::new(content)
                 .wrap(Wrap { trim: true })
-                .block(Block::new().borders(Borders::ALL)
-                    .border_set(border::THICK)
-                    .title(instruction.alignment(Alignment::Center).position(Position::Top))),
-            layout[1]);
+                .block(block),
+            encounter_area);
 
         match &self.popup {
             NoPopUp => {}
             ChallengeSuccessfulPopup => {
                 let popup_area = centered_rect(area, 30, 30);
                 let today_card = self.today_card.as_ref().unwrap();
-                let content = format!("是否挑战成功？{}\n\n选择: \n{:?}", today_card.title, today_card.choices[self.selected_choice.unwrap()].description);
-                let popup = Popup::new(content, "".to_string(), " <Y> 成功 || <N> 失败 || <P> 键回退 ".to_string());
+                let content = tr!("是否挑战成功？{}\n\n选择: \n{}", "Did the challenge succeed? {}\n\nChoice: \n{}",
+                                  today_card.title, today_card.choices[self.selected_choice.unwrap()].description);
+                let popup = Popup::new(content, "".to_string(), tr!(" <Y> 成功 || <N> 失败 || <P> 键回退 ", " <Y> Succeeded || <N> Failed || <P> Back "));
+                frame.render_widget(popup, popup_area);
+            }
+            BossFightPopup => {
+                let popup_area = centered_rect(area, 30, 30);
+                let tyrant_card = &self.deck.as_ref().unwrap().tyrant_card;
+                let content = tr!("是否赢得Boss战？\n\n{}", "Did you win the tyrant battle?\n\n{}", tyrant_card.battle_title);
+                let popup = Popup::new(content, tr!(" Boss战 ", " Tyrant battle "), tr!(" <Y> 胜利 || <N> 失败 || <P> 键回退 ", " <Y> Victory || <N> Defeat || <P> Back "));
+                frame.render_widget(popup, popup_area);
+            }
+            ExportPopup => {
+                let popup_area = centered_rect(area, 30, 30);
+                let content = tr!("导出战斗记录（共{}条）\n\n<M> Markdown\n<C> CSV\n<J> JSON", "Export the battle log ({} entries)\n\n<M> Markdown\n<C> CSV\n<J> JSON", self.battle_logs.len());
+                let popup = Popup::new(content, tr!(" 导出 ", " Export "), tr!(" <P> 键回退 ", " <P> Back "));
+                frame.render_widget(popup, popup_area);
+            }
+            BaddieQueuePopup => {
+                let popup_area = centered_rect(area, 50, 60);
+                let queue = &self.baddie_queue;
+                let tyrant_card = &self.deck.as_ref().unwrap().tyrant_card;
+                let mut content = String::new();
+                if let Some(choice) = self.today_card.as_ref().and_then(|card| card.choices.get(queue.choice)) {
+                    content += tr!("选择{}：{}\n行动：{}\n\n", "Choice {}: {}\nAction: {}\n\n", queue.choice + 1, choice.description, choice.action).as_str();
+                }
+                content += tr!("反派点数：{}（第{}天 × {}人队伍，按卡牌调整）\n怪物类型：{}\n\n", "Baddie points: {} (day {} × party of {}, adjust for the card)\nBaddies: {}\n\n",
+                               queue.points, self.days, self.party.len().max(1), tyrant_card.creatures).as_str();
+                if queue.baddies.is_empty() {
+                    content += tr!("没有反派。", "No baddie.").as_str();
+                }
+                for (idx, baddie) in queue.baddies.iter().enumerate() {
+                    content += tr!("{}. {}（{}点）\n", "{}. {} ({} points)\n", idx + 1, baddie.name, baddie.points).as_str();
+                }
+                let popup = Popup::new(content, tr!(" 反派队列（BQ） ", " Baddie Queue "),
+                                       tr!(" <1-3> 选择 || <+/-> 反派点数 || <R> 重新抽取 || <P> 键回退 ", " <1-3> Choice || <+/-> Baddie points || <R> Reroll || <P> Back "));
+                frame.render_widget(popup, popup_area);
+            }
+            DicePopup => {
+                let popup_area = centered_rect(area, 40, 50);
+                let input = if self.dice_input.is_empty() { "d6" } else { self.dice_input.as_str() };
+                let mut content = tr!("骰子：{}\n", "Dice: {}\n", input);
+                if let Some(gearloc) = self.party.get(self.selected_gearloc) {
+                    content += tr!("健康检查：{}（生命{}）\n", "Health check: {} ({} health)\n", self.gearloc_name(&gearloc.id), gearloc.health).as_str();
+                }
+                content += tr!("\n今天的掷骰：\n", "\nToday's rolls:\n").as_str();
+                for roll in self.pending_rolls.iter() {
+                    content += format!("* {}\n", self.describe_roll(roll)).as_str();
+                }
+                let popup = Popup::new(content, tr!(" 骰子 ", " Dice "),
+                                       tr!(" <0-9/D> 输入骰子 || <Enter> 掷骰 || <Tab> 选择Gearloc || <C> 健康检查 || <P> 键回退 ",
+                                           " <0-9/D> Type the dice || <Enter> Roll || <Tab> Select the Gearloc || <C> Health check || <P> Back "));
                 frame.render_widget(popup, popup_area);
             }
             BreakPopup(content) => {
                 let popup_area = centered_rect(area, 40, 40);
                 let mut content = match content {
-                    None => { "休息一下......".to_string() }
+                    None => { tr!("休息一下......", "Take a break......") }
                     Some(word) => { word.clone() }
                 };
-                content = content + "\n\n\n<a> 无操作进入下一天。\n<b> 将当前遭遇卡放置牌堆顶部。\n<c> 将卡组中第一个暴君遭遇卡置顶。洗剩余的卡。\n<d> 将当前遭遇卡洗入牌堆。\n<e> 为今天抽取新的遭遇卡，并把当前遭遇卡洗入牌堆。\n";
-                for (i, card) in SPECIAL_ENCOUNTER_CARDS.lock().unwrap().iter().enumerate() {
+                content += tr!("\n\n\n<a> 无操作进入下一天。\n<b> 将当前遭遇卡放置牌堆顶部。\n<c> 将卡组中第一个暴君遭遇卡置顶。洗剩余的卡。\n<d> 将当前遭遇卡洗入牌堆。\n<e> 为今天抽取新的遭遇卡，并把当前遭遇卡洗入牌堆。\n",
+                               "\n\n\n<a> Go to the next day.\n<b> Put the current encounter card on top of the deck.\n<c> Put the first tyrant encounter card of the deck on top. Shuffle the rest.\n<d> Shuffle the current encounter card into the deck.\n<e> Draw a new encounter card for today, and shuffle the current encounter card into the deck.\n").as_str();
+                for (i, card) in self.deck.as_ref().unwrap().pool.special_encounter_cards.iter().enumerate() {
                     let code_point = 'f' as u32;
                     let new_code_point = code_point + i as u32;
                     let new_char = std::char::from_u32(new_code_point).unwrap();
-                    content += format!("<{}> 将特殊遭遇卡-“{}”洗入牌堆。\n", new_char, card.title).as_str()
+                    content += tr!("<{}> 将特殊遭遇卡-“{}”洗入牌堆。\n", "<{}> Shuffle the special encounter card \"{}\" into the deck.\n", new_char, card.title).as_str()
                 }
-                let popup = Popup::new(content, "".to_string(), " <O> 键挑战Boss ".to_string());
+                let popup = Popup::new(content, "".to_string(), tr!(" <O> 键挑战Boss ", " <O> Challenge the tyrant "));
                 frame.render_widget(popup, popup_area);
             }
         }
@@ -350,32 +1039,88 @@ impl Component for GamePage {
     }
 }
 
-fn build_encounter_content(card: &EncounterCard, days: usize) -> String {
+/// The text of the encounter card. In bilingual mode every card text is followed by its translation.
+fn build_encounter_content(card: &EncounterCard, days: usize, bilingual: bool) -> String {
+    let show = |text: &LocalizedText| if bilingual { text.bilingual() } else { text.text().to_string() };
     let mut content = String::new();
-    content = content + format!("第{}天：", days).as_str() + card.title.as_str() + "\n\n-------------------------------------\n\n";
-    content = content + card.story.as_str() + "\n\n-------------------------------------\n\n";
-    content = content + "选择：\n\n";
+    content = content + tr!("第{}天：", "Day {}: ", days).as_str() + show(&card.title).as_str() + "\n\n-------------------------------------\n\n";
+    content = content + show(&card.story).as_str() + "\n\n-------------------------------------\n\n";
+    content += tr!("选择：\n\n", "Choices:\n\n").as_str();
     for c in &card.choices {
-        content = content + "*" + c.description.as_str() + "\n";
-        content = content + "行动：" + c.action.as_str() + "\n";
-        content = content + "奖励：" + c.rewards.as_str() + "\n\n";
+        content = content + "*" + show(&c.description).as_str() + "\n";
+        content = content + tr!("行动：", "Action: ").as_str() + show(&c.action).as_str() + "\n";
+        content = content + tr!("奖励：", "Rewards: ").as_str() + show(&c.rewards).as_str() + "\n";
+        if let Some(effects) = describe_effects(&c.effects) {
+            content = content + tr!("自动效果：", "Applied by the app: ").as_str() + effects.as_str() + "\n";
+        }
+        content += "\n";
+    }
+    content = content + show(&card.remark).as_str() + "\n\n";
+    content += tr!("进度：\n", "Progress:\n").as_str();
+    for idx in 0..card.choices.len() {
+        content += tr!("* 选择{}：获取{}进度。\n", "* Choice {}: gain {} progress.\n", idx + 1, card.choice_progress(idx)).as_str();
+    }
+    content
+}
+
+/// Heal or damage a Gearloc by the given health, negative for damage.
+fn change_health(gearloc: &mut GearlocState, health: i32) {
+    for _ in 0..health.unsigned_abs() {
+        if health > 0 { gearloc.heal() } else { gearloc.damage() }
+    }
+}
+
+/// The effects of a choice applied by the app, e.g. "Baddie points +4/+4/+8/+8, health -2, draws another encounter".
+fn describe_effects(effects: &Effects) -> Option<String> {
+    let mut parts = Vec::new();
+    if !effects.baddie_points.is_empty() {
+        let points: Vec<String> = effects.baddie_points.iter().map(|points| format!("{:+}", points)).collect();
+        parts.push(tr!("反派点数{}（1/2/3/4人）", "baddie points {} (1/2/3/4 Gearlocs)", points.join("/")));
+    }
+    if let Some(skill_points) = effects.skill_points {
+        parts.push(tr!("每个Gearloc{}技能点", "{} skill points each", skill_points));
     }
-    content = content + card.remark.as_str() + "\n\n";
-    content = content + "进度：\n";
-    for (idx, p) in card.progress.iter().enumerate() {
-        content = content + format!("* 选择{}：获取{}进度。\n", idx + 1, p).as_str();
+    if effects.loot > 0 {
+        parts.push(tr!("每个Gearloc{}战利品", "{} loot each", effects.loot));
+    }
+    if effects.trove_loot > 0 {
+        parts.push(tr!("每个Gearloc{}宝藏战利品", "{} trove loot each", effects.trove_loot));
+    }
+    if effects.health != 0 {
+        parts.push(tr!("每个Gearloc生命{:+}", "health {:+} each", effects.health));
+    }
+    if effects.draw_another_encounter {
+        parts.push(tr!("为今天抽取另一个遭遇", "draws another encounter for today"));
+    }
+    (!parts.is_empty()).then(|| parts.join(tr!("，", ", ").as_str()))
+}
+
+fn build_summary(battle_logs: &[BattleLog], progress: usize, outcome: CampaignOutcome) -> String {
+    let success = battle_logs.iter().filter(|log| log.success).count();
+    let result = match outcome {
+        CampaignOutcome::InProgress => tr!("等待Boss战", "waiting for the tyrant battle"),
+        CampaignOutcome::Victory => tr!("胜利", "victory"),
+        CampaignOutcome::Defeat => tr!("失败", "defeat"),
+    };
+    let mut content = tr!("战役结果：{}\n", "Campaign result: {}\n", result);
+    content += tr!("战斗总结：共{}场战斗，成功{}场，失败{}场，获得{}进度。\n\n", "Battle summary: {} battles, {} succeeded, {} failed, {} progress gained.\n\n",
+                   battle_logs.len(), success, battle_logs.len() - success, progress).as_str();
+    for log in battle_logs {
+        content += tr!("第{}天：{} - {} {}\n", "Day {}: {} - {} {}\n", log.day, log.title, log.choice.description, if log.success { "✅" } else { "❌" }).as_str();
     }
     content
 }
 
-fn build_battle_log_menu(battle_log: &Vec<BattleLog>) -> Table {
+/// The battle log table. `rolls` are the dice rolls of each log for display.
+fn build_battle_log_menu(battle_log: &[BattleLog], rolls: Vec<String>) -> Table<'_> {
     let mut rows: Vec<Row> = Vec::new();
-    for log in battle_log {
+    for (log, rolls) in battle_log.iter().zip(rolls) {
         let r = Row::new(vec![log.day.to_string(),
-                              log.title.clone(),
-                              log.choice.description.clone(),
+                              if log.redrawn { tr!("{}（已重抽）", "{} (redrawn)", log.title) } else { log.title.to_string() },
+                              log.choice.description.to_string(),
                               log.progress.to_string(),
-                              if log.success { log.choice.rewards.clone() } else { "无奖励".to_string() },
+                              if log.success { log.choice.rewards.to_string() } else { tr!("无奖励", "No rewards") },
+                              rolls,
                               if log.success { "✅".to_string() } else { "❌".to_string() }]).height(2);
         rows.push(r);
     }
@@ -385,11 +1130,12 @@ fn build_battle_log_menu(battle_log: &Vec<BattleLog>) -> Table {
         Constraint::Max(50),
         Constraint::Length(10),
         Constraint::Max(200),
+        Constraint::Max(40),
         Constraint::Length(10)
     ];
 
     let block = Block::default()
-        .title(Title::from(" <Enter> 键回滚".bold()).alignment(Alignment::Center).position(Position::Bottom))
+        .title(Title::from(tr!(" <Enter> 键回滚", " <Enter> Roll back").bold()).alignment(Alignment::Center).position(Position::Bottom))
         .borders(Borders::ALL)
         .padding(Padding::top(1))
         .border_set(border::THICK);
@@ -398,7 +1144,7 @@ fn build_battle_log_menu(battle_log: &Vec<BattleLog>) -> Table {
         .column_spacing(1)
         .style(Style::new().white())
         .header(
-            Row::new(vec!["天数", "战斗", "选择", "进度", "奖励", "是否成功"])
+            Row::new(vec![tr!("天数", "Day"), tr!("战斗", "Battle"), tr!("选择", "Choice"), tr!("进度", "Progress"), tr!("奖励", "Rewards"), tr!("骰子", "Dice"), tr!("是否成功", "Success")])
                 .style(Style::new().bold())
                 .bottom_margin(1),
         )
@@ -411,5 +1157,9 @@ fn build_battle_log_menu(battle_log: &Vec<BattleLog>) -> Table {
 enum ShowPopup {
     NoPopUp,
     ChallengeSuccessfulPopup,
+    BossFightPopup,
+    ExportPopup,
     BreakPopup(Option<String>),
+    BaddieQueuePopup,
+    DicePopup,
 }
\ No newline at end of file
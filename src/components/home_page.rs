@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyCode;
 use ratatui::Frame;
@@ -12,29 +13,45 @@ use ratatui::symbols::border;
 use ratatui::widgets::{Block, Borders, List, ListDirection, ListItem, ListState, Padding};
 use ratatui::widgets::block::{Position, Title};
 use tracing::info;
-use crate::app::Action::{Quit, Render};
-use crate::components::{select_boss_page};
+use crate::app::Action::{Load, Quit, Render, Update};
+use crate::components::{diagnostics_page, game_page, load_game_page, select_boss_page, statistics_page};
+use crate::components::popup::Popup;
+use crate::core::i18n::{language, set_language, tr};
+use crate::core::settings::{load_settings, save_settings, Settings};
 use crate::utils::{centered_rect};
 
 /// Home page for the game. Main menu. Menu items:
 /// - 开始游戏: Start the game. Emit `Render("SelectBossPage")` event to let APP render the next page.
+/// - 继续游戏: Continue a saved game. Emit `Update("LoadGamePage")` to refresh the save list and `Render("LoadGamePage")`.
+/// - 统计数据: Show the statistics of the finished campaigns. Emit `Update("StatisticsPage")` and `Render("StatisticsPage")`.
+/// - 诊断信息: Show the content directory search and the resolved paths. Emit `Render("DiagnosticsPage")`.
+/// - 语言: Switch the language of the UI and the cards. The language is kept in the settings for the next start.
 /// - 退出: exits the application.
+///
+/// If the previous session exited uncleanly, a popup offers to restore the autosave with `Load("GamePage", path)`.
+/// The cards that failed to load are listed in an error popup. Other pages can add an error with `Update("HomePage", message)`.
 pub const NAME: &str = "HomePage";
 
+const MENU_SIZE: usize = 6;
+
 pub struct HomePage {
     pub name: String,
     pub action_sender: Option<UnboundedSender<Action>>,
     menu_select_state: ListState,
+    recovery: Option<PathBuf>, // The autosave left by a crashed session
+    errors: Vec<String>,       // The errors shown in the error popup
 }
 
 impl HomePage {
-    pub fn new() -> Self {
+    pub fn new(recovery: Option<PathBuf>, errors: Vec<String>) -> Self {
         let mut state = ListState::default();
         state.select(Some(0));
         HomePage {
             name: NAME.to_string(),
             action_sender: None,
             menu_select_state: state,
+            recovery,
+            errors,
         }
     }
 }
@@ -46,15 +63,32 @@ impl Component for HomePage {
     }
 
     fn handle_key_events(&mut self, key: KeyEvent) -> Result<()> {
+        if let Some(autosave) = self.recovery.clone() {
+            if key.code == KeyCode::Char('y') {
+                info!("[{}] Restoring autosave {:?}", self.name, &autosave);
+                self.action_sender.as_mut().unwrap().send(Load(game_page::NAME.to_string(), autosave.clone()))?;
+                self.recovery = None;
+            }
+            if key.code == KeyCode::Char('n') {
+                info!("[{}] Ignored autosave {:?}", self.name, autosave);
+                self.recovery = None;
+            }
+            return Ok(());
+        }
+        if !self.errors.is_empty() {
+            if key.code == KeyCode::Enter {
+                info!("[{}] Closed the error popup", self.name);
+                self.errors.clear();
+            }
+            return Ok(());
+        }
         let mut idx = self.menu_select_state.selected().unwrap();
         if key.code == KeyCode::Up {
-            if idx > 0 {
-                idx -= 1;
-            }
+            idx = idx.saturating_sub(1);
             self.menu_select_state.select(Some(idx));
         }
         if key.code == KeyCode::Down {
-            if idx < 3 {
+            if idx + 1 < MENU_SIZE {
                 idx += 1;
             }
             self.menu_select_state.select(Some(idx));
@@ -66,6 +100,29 @@ impl Component for HomePage {
                     self.action_sender.as_mut().unwrap().send(Render(select_boss_page::NAME.to_string()))?;
                 }
                 1 => {
+                    info!("[{}] Selected 继续游戏", self.name);
+                    self.action_sender.as_mut().unwrap().send(Update(load_game_page::NAME.to_string(), String::new()))?;
+                    self.action_sender.as_mut().unwrap().send(Render(load_game_page::NAME.to_string()))?;
+                }
+                2 => {
+                    info!("[{}] Selected 统计数据", self.name);
+                    self.action_sender.as_mut().unwrap().send(Update(statistics_page::NAME.to_string(), String::new()))?;
+                    self.action_sender.as_mut().unwrap().send(Render(statistics_page::NAME.to_string()))?;
+                }
+                3 => {
+                    info!("[{}] Selected 诊断信息", self.name);
+                    self.action_sender.as_mut().unwrap().send(Render(diagnostics_page::NAME.to_string()))?;
+                }
+                4 => {
+                    let language = language().next();
+                    info!("[{}] Selected 语言: {:?}", self.name, language);
+                    set_language(language);
+                    let settings = Settings { language, ..load_settings().unwrap_or_default() };
+                    if let Err(e) = save_settings(&settings) {
+                        info!("[{}] Failed to save the settings: {}", self.name, e);
+                    }
+                }
+                5 => {
                     info!("[{}] Selected 退出", self.name);
                     self.action_sender.as_mut().unwrap().send(Quit)?;
                 }
@@ -75,6 +132,13 @@ impl Component for HomePage {
         Ok(())
     }
 
+    fn update(&mut self, action: Action) -> Result<()> {
+        if let Update(_, message) = action {
+            self.errors.push(message);
+        }
+        Ok(())
+    }
+
     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
         // Banner
         let tmb_banner = BigText::builder()
@@ -86,17 +150,17 @@ impl Component for HomePage {
             .alignment(Alignment::Center)
             .build()?;
         // Border
-        let instruction = Title::from(" <Enter> 键选择 || <Q> 键强制退出".bold());
+        let instruction = Title::from(tr!(" <Enter> 键选择 || <Q> 键强制退出", " <Enter> Select || <Q> Quit").bold());
         let block = Block::default()
             .title(instruction.alignment(Alignment::Center).position(Position::Bottom))
             .borders(Borders::ALL)
             .padding(Padding::top(1))
             .border_set(border::THICK);
         // Menu
-        let menu_items: Vec<ListItem> = vec![ListItem::new("开始新游戏"), ListItem::new("退出")];
+        let menu_items: Vec<ListItem> = menu_items().into_iter().map(ListItem::new).collect();
 
         let list = List::new(menu_items)
-            .block(Block::bordered().title(Title::from(" 主菜单 ".bold()).alignment(Alignment::Center)))
+            .block(Block::bordered().title(Title::from(tr!(" 主菜单 ", " Main menu ").bold()).alignment(Alignment::Center)))
             .style(Style::default().fg(Color::White))
             .highlight_style(Style::default().yellow().bold())
             .highlight_symbol(" ☠️ ")
@@ -118,6 +182,28 @@ impl Component for HomePage {
         f.render_widget(block, area);
         f.render_widget(tmb_banner, layout[0]);
         f.render_stateful_widget(list, centered_rect(layout[1], 50, 50), &mut self.menu_select_state);
+
+        if self.recovery.is_some() {
+            let content = tr!("上次游戏没有正常退出，是否恢复自动存档？", "The last game didn't exit normally. Restore the autosave?");
+            let popup = Popup::new(content, tr!(" 恢复游戏 ", " Restore "), tr!(" <Y> 恢复 || <N> 忽略 ", " <Y> Restore || <N> Ignore "));
+            f.render_widget(popup, centered_rect(area, 40, 30));
+        } else if !self.errors.is_empty() {
+            let content = self.errors.iter().map(|e| format!("* {}", e)).collect::<Vec<String>>().join("\n");
+            let popup = Popup::new(content, tr!(" 错误 ", " Errors "), tr!(" <Enter> 键关闭 ", " <Enter> Close "));
+            f.render_widget(popup, centered_rect(area, 70, 60));
+        }
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// The menu items in the current language. The language item shows the current language.
+fn menu_items() -> [String; MENU_SIZE] {
+    [
+        tr!("开始新游戏", "New game"),
+        tr!("继续游戏", "Continue"),
+        tr!("统计数据", "Statistics"),
+        tr!("诊断信息", "Diagnostics"),
+        tr!("语言：{}", "Language: {}", language().name()),
+        tr!("退出", "Quit"),
+    ]
+}
@@ -0,0 +1,145 @@
+use std::path::PathBuf;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::prelude::*;
+use ratatui::symbols::border;
+use ratatui::widgets::{Block, Borders, Padding, Row, Table, TableState};
+use ratatui::widgets::block::{Position, Title};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::info;
+use crate::app::Action;
+use crate::app::Action::{Load, Render};
+use crate::component::Component;
+use crate::components::{game_page, home_page};
+use crate::core::i18n::tr;
+use crate::core::save::list_saves;
+
+/// Lists the saved campaigns. Emit `Load("GamePage", path)` to let `GamePage` restore the selected save.
+/// The save list is refreshed every time the page receives an `Update` action.
+pub const NAME: &str = "LoadGamePage";
+
+pub struct LoadGamePage {
+    pub name: String,
+    pub action_sender: Option<UnboundedSender<Action>>,
+    menu_select_state: TableState,
+    saves: Vec<SaveItem>,
+}
+
+struct SaveItem {
+    path: PathBuf,
+    saved_at: String,
+    tyrant_name: String,
+    days: usize,
+    progress: usize,
+}
+
+impl LoadGamePage {
+    pub fn new() -> Self {
+        let mut state = TableState::default();
+        state.select(Some(0));
+        LoadGamePage {
+            name: NAME.to_string(),
+            action_sender: None,
+            menu_select_state: state,
+            saves: Vec::new(),
+        }
+    }
+
+    fn refresh(&mut self) {
+        self.saves = list_saves().into_iter()
+            .map(|(path, save)| SaveItem {
+                path,
+                saved_at: save.saved_at,
+                tyrant_name: save.deck.tyrant_card.name.to_string(),
+                days: save.days,
+                progress: save.progress,
+            })
+            .collect();
+        self.menu_select_state.select(Some(0));
+        info!("[{}] Found {} saves", self.name, self.saves.len());
+    }
+}
+
+impl Component for LoadGamePage {
+    fn register_action_handler(&mut self, sender: UnboundedSender<Action>) -> color_eyre::Result<()> {
+        self.action_sender = Some(sender);
+        Ok(())
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> color_eyre::Result<()> {
+        let mut idx = self.menu_select_state.selected().unwrap();
+        if key.code == KeyCode::Up {
+            idx = idx.saturating_sub(1);
+            self.menu_select_state.select(Some(idx));
+        }
+        if key.code == KeyCode::Down {
+            if idx + 1 < self.saves.len() {
+                idx += 1;
+            }
+            self.menu_select_state.select(Some(idx));
+        }
+        if key.code == KeyCode::Char('p') {
+            self.action_sender.as_mut().unwrap().send(Render(home_page::NAME.to_string()))?;
+        }
+        if key.code == KeyCode::Enter {
+            if let Some(save) = self.saves.get(idx) {
+                let load = Load(game_page::NAME.to_string(), save.path.clone());
+                info!("[{}] Sending action: {:?}", self.name, load);
+                self.action_sender.as_mut().unwrap().send(load)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> color_eyre::Result<()> {
+        if let Action::Update(_, _) = action {
+            self.refresh();
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> color_eyre::Result<()> {
+        let mut rows: Vec<Row> = Vec::new();
+        for save in self.saves.iter() {
+            let r = Row::new(vec![save.saved_at.clone(),
+                                  save.tyrant_name.clone(),
+                                  save.days.to_string(),
+                                  save.progress.to_string(),
+                                  save.path.file_name().unwrap_or_default().to_string_lossy().to_string()]).height(2);
+            rows.push(r);
+        }
+        if rows.is_empty() {
+            rows.push(Row::new(vec![tr!("没有找到存档", "No save found")]));
+        }
+
+        let widths = [
+            Constraint::Length(25),
+            Constraint::Length(15),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Max(100),
+        ];
+        let block = Block::default()
+            .title(Title::from(tr!(" 继续游戏 ", " Continue ")).alignment(Alignment::Center).position(Position::Top))
+            .title(Title::from(tr!(" <P> 键回退上一页 || <Enter> 键读取存档 || <Q> 键强制退出", " <P> Back || <Enter> Load the save || <Q> Quit")).alignment(Alignment::Center).position(Position::Bottom))
+            .borders(Borders::ALL)
+            .padding(Padding::top(1))
+            .border_set(border::THICK);
+
+        let table = Table::new(rows, widths)
+            .column_spacing(1)
+            .style(Style::new().white())
+            .header(
+                Row::new(vec![tr!("保存时间", "Saved at"), tr!("Boss", "Tyrant"), tr!("天数", "Days"), tr!("进度", "Progress"), tr!("存档文件", "Save file")])
+                    .style(Style::new().bold())
+                    .bottom_margin(1),
+            )
+            .block(block)
+            .highlight_style(Style::new().yellow())
+            .highlight_symbol(" >> ");
+
+        f.render_stateful_widget(table, area, &mut self.menu_select_state);
+        Ok(())
+    }
+}
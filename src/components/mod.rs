@@ -1,4 +1,8 @@
 pub mod home_page;
 pub mod select_boss_page;
+pub mod party_setup_page;
 pub mod game_page;
+pub mod load_game_page;
+pub mod statistics_page;
+pub mod diagnostics_page;
 mod popup;
\ No newline at end of file
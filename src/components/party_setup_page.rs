@@ -0,0 +1,156 @@
+use std::sync::Arc;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::prelude::*;
+use ratatui::symbols::border;
+use ratatui::widgets::{Block, Borders, Padding, Row, Table, TableState};
+use ratatui::widgets::block::{Position, Title};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::info;
+use crate::app::Action;
+use crate::app::Action::{NewGame, Render};
+use crate::component::Component;
+use crate::components::{game_page, select_boss_page};
+use crate::core::game::{CardLibrary, GameSetup};
+use crate::core::i18n::tr;
+use crate::core::party::MAX_PARTY_SIZE;
+
+/// Lists the Gearlocs of the roster to pick the party of 1 to 4 Gearlocs. Receives the setup chosen on
+/// `SelectBossPage` with `NewGame("PartySetupPage", setup)`, adds the party and emits `NewGame("GamePage", setup)` and
+/// `Render("GamePage")` to start. The party is kept for the next games.
+pub const NAME: &str = "PartySetupPage";
+
+pub struct PartySetupPage {
+    pub name: String,
+    pub action_sender: Option<UnboundedSender<Action>>,
+    library: Arc<CardLibrary>,
+    menu_select_state: TableState,
+    setup: Option<GameSetup>, // The setup of the game to start
+    party: Vec<String>, // The ids of the selected Gearlocs, in the order of selection
+}
+
+impl PartySetupPage {
+    pub fn new(library: Arc<CardLibrary>) -> Self {
+        let mut state = TableState::default();
+        state.select(Some(0));
+        PartySetupPage {
+            name: NAME.to_string(),
+            action_sender: None,
+            library,
+            menu_select_state: state,
+            setup: None,
+            party: Vec::new(),
+        }
+    }
+
+    fn toggle(&mut self, id: &str) {
+        if let Some(idx) = self.party.iter().position(|selected| selected == id) {
+            self.party.remove(idx);
+        } else if self.party.len() < MAX_PARTY_SIZE {
+            self.party.push(id.to_string());
+        }
+        info!("[{}] Party: {:?}", self.name, self.party);
+    }
+}
+
+impl Component for PartySetupPage {
+    fn register_action_handler(&mut self, sender: UnboundedSender<Action>) -> color_eyre::Result<()> {
+        self.action_sender = Some(sender);
+        Ok(())
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> color_eyre::Result<()> {
+        let mut idx = self.menu_select_state.selected().unwrap();
+        if key.code == KeyCode::Up {
+            idx = idx.saturating_sub(1);
+            self.menu_select_state.select(Some(idx));
+        }
+        if key.code == KeyCode::Down {
+            if idx + 1 < self.library.gearlocs.len() {
+                idx += 1;
+            }
+            self.menu_select_state.select(Some(idx));
+        }
+        if key.code == KeyCode::Char(' ') {
+            if let Some(gearloc) = self.library.gearlocs.get(idx) {
+                let id = gearloc.id.clone();
+                self.toggle(&id);
+            }
+        }
+        if key.code == KeyCode::Char('p') {
+            self.action_sender.as_mut().unwrap().send(Render(select_boss_page::NAME.to_string()))?;
+        }
+        if key.code == KeyCode::Enter && !self.party.is_empty() {
+            if let Some(mut setup) = self.setup.take() {
+                setup.party = self.party.clone();
+                let new_game = NewGame(game_page::NAME.to_string(), setup);
+                let render = Render(game_page::NAME.to_string());
+                info!("[{}] Sending action: {:?}, {:?}", self.name, new_game, render);
+                self.action_sender.as_mut().unwrap().send(new_game)?;
+                self.action_sender.as_mut().unwrap().send(render)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> color_eyre::Result<()> {
+        if let NewGame(_, setup) = action {
+            // Keep the Gearlocs of the last game that are still in the roster
+            self.party.retain(|id| self.library.gearloc(id).is_some());
+            self.setup = Some(setup);
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> color_eyre::Result<()> {
+        let mut rows: Vec<Row> = Vec::new();
+        for gearloc in self.library.gearlocs.iter() {
+            let selected = match self.party.iter().position(|id| *id == gearloc.id) {
+                Some(idx) => format!("[{}]", idx + 1),
+                None => "[ ]".to_string(),
+            };
+            let r = Row::new(vec![selected,
+                                  gearloc.name.to_string(),
+                                  gearloc.health.to_string(),
+                                  gearloc.description.to_string()]).height(2);
+            rows.push(r);
+        }
+
+        let widths = [
+            Constraint::Length(8),
+            Constraint::Length(15),
+            Constraint::Length(10),
+            Constraint::Min(50),
+        ];
+        let party = self.library.party_names(&self.party).join(", ");
+        let status = if self.party.is_empty() {
+            tr!(" 至少选择一个Gearloc ", " Select at least one Gearloc ")
+        } else {
+            tr!(" 队伍（{}/{}）：{} ", " Party ({}/{}): {} ", self.party.len(), MAX_PARTY_SIZE, party)
+        };
+        let block = Block::default()
+            .title(Title::from(tr!(" 选择队伍 ", " Select the party ")).alignment(Alignment::Center).position(Position::Top))
+            .title(Title::from(status).alignment(Alignment::Left).position(Position::Bottom))
+            .title(Title::from(tr!(" <P> 键回退上一页 || <Space> 键选择Gearloc || <Enter> 键开始游戏 || <Q> 键强制退出",
+                                   " <P> Back || <Space> Select the Gearloc || <Enter> Start the game || <Q> Quit")).alignment(Alignment::Center).position(Position::Bottom))
+            .borders(Borders::ALL)
+            .padding(Padding::top(1))
+            .border_set(border::THICK);
+
+        let table = Table::new(rows, widths)
+            .column_spacing(1)
+            .style(Style::new().white())
+            .header(
+                Row::new(vec![tr!("队伍", "Party"), tr!("Gearloc", "Gearloc"), tr!("生命值", "Health"), tr!("简介", "Description")])
+                    .style(Style::new().bold())
+                    .bottom_margin(1),
+            )
+            .block(block)
+            .highlight_style(Style::new().yellow())
+            .highlight_symbol(" >> ");
+
+        f.render_stateful_widget(table, area, &mut self.menu_select_state);
+        Ok(())
+    }
+}
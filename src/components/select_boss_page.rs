@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Rect};
@@ -8,31 +9,47 @@ use ratatui::widgets::block::{Position, Title};
 use tokio::sync::mpsc::UnboundedSender;
 use tracing::info;
 use crate::app::Action;
-use crate::app::Action::{Render, Update};
+use crate::app::Action::{NewGame, Render};
 use crate::component::Component;
-use crate::components::{game_page, home_page};
+use crate::components::{home_page, party_setup_page};
 use crate::components::popup::Popup;
-use crate::core::game::{TYRANT_CARDS};
+use crate::core::game::{CardLibrary, GameSetup, pack_name};
+use crate::core::i18n::tr;
 use crate::utils::centered_rect;
 
+/// Lists the tyrants. Selecting a tyrant shows its introduction popup with the seed input, then the content pack popup.
+/// The selected packs are kept for the next games. Emit `NewGame("PartySetupPage", setup)` and `Render("PartySetupPage")`
+/// to pick the party next.
 pub const NAME: &str = "SelectBossPage";
 
 pub struct SelectBossPage {
     pub name: String,
     pub action_sender: Option<UnboundedSender<Action>>,
+    library: Arc<CardLibrary>,
     menu_select_state: TableState,
-    is_popup: bool,
+    popup: ShowPopup,
+    seed_input: String, // The seed typed in the boss popup. Empty means a random seed.
+    packs: Vec<(String, bool)>, // The content packs and whether they are selected
+}
+
+enum ShowPopup {
+    NoPopUp,
+    BossPopup,
+    PackPopup,
 }
 
 impl SelectBossPage {
-    pub fn new() -> Self {
+    pub fn new(library: Arc<CardLibrary>) -> Self {
         let mut state = TableState::default();
         state.select(Some(0));
         SelectBossPage {
             name: NAME.to_string(),
             action_sender: None,
             menu_select_state: state,
-            is_popup: false,
+            popup: ShowPopup::NoPopUp,
+            seed_input: String::new(),
+            packs: library.packs().into_iter().map(|pack| (pack, true)).collect(),
+            library,
         }
     }
 }
@@ -45,32 +62,66 @@ impl Component for SelectBossPage {
 
     fn handle_key_events(&mut self, key: KeyEvent) -> color_eyre::Result<()> {
         let mut idx = self.menu_select_state.selected().unwrap();
-        let tyrant_cards = TYRANT_CARDS.lock().unwrap();
-        let tyrant_card = tyrant_cards.get(idx).unwrap();
+        let tyrant_cards = &self.library.tyrant_cards;
+        let Some(tyrant_card) = tyrant_cards.get(idx) else {
+            // No tyrant card is loaded. The errors are shown on the home page.
+            if key.code == KeyCode::Char('p') {
+                self.action_sender.as_mut().unwrap().send(Render(home_page::NAME.to_string()))?;
+            }
+            return Ok(());
+        };
 
-        match self.is_popup {
-            true => {
+        match self.popup {
+            ShowPopup::BossPopup => {
                 if key.code == KeyCode::Char('p') {
-                    self.is_popup = false;
+                    self.popup = ShowPopup::NoPopUp;
+                }
+                if let KeyCode::Char(c) = key.code {
+                    // u64 has at most 20 digits, keep one digit less to avoid overflow
+                    if c.is_ascii_digit() && self.seed_input.len() < 19 {
+                        self.seed_input.push(c);
+                    }
+                }
+                if key.code == KeyCode::Backspace {
+                    self.seed_input.pop();
                 }
                 if key.code == KeyCode::Enter {
-                    self.is_popup = false;
-                    let update = Update(game_page::NAME.to_string(), tyrant_card.id.clone());
-                    let render = Render(game_page::NAME.to_string());
-                    info!("[{}] Sending action: {:?}, {:?}", self.name, update, render);
-                    self.action_sender.as_mut().unwrap().send(update)?;
+                    self.popup = ShowPopup::PackPopup;
+                }
+            }
+            ShowPopup::PackPopup => {
+                if key.code == KeyCode::Char('p') {
+                    self.popup = ShowPopup::BossPopup;
+                }
+                if let KeyCode::Char(c) = key.code {
+                    if let Some(pack) = self.packs.get_mut((c as usize).wrapping_sub('a' as usize)) {
+                        pack.1 = !pack.1;
+                        info!("[{}] Toggled content pack {}: {}", self.name, pack.0, pack.1);
+                    }
+                }
+                if key.code == KeyCode::Enter && self.packs.iter().any(|(_, selected)| *selected) {
+                    self.popup = ShowPopup::NoPopUp;
+                    let setup = GameSetup {
+                        tyrant_id: tyrant_card.id.clone(),
+                        seed: self.seed_input.parse().ok(),
+                        packs: self.packs.iter().filter(|(_, selected)| *selected).map(|(pack, _)| pack.clone()).collect(),
+                        party: Vec::new(),
+                    };
+                    let new_game = NewGame(party_setup_page::NAME.to_string(), setup);
+                    let render = Render(party_setup_page::NAME.to_string());
+                    info!("[{}] Sending action: {:?}, {:?}", self.name, new_game, render);
+                    self.action_sender.as_mut().unwrap().send(new_game)?;
                     self.action_sender.as_mut().unwrap().send(render)?;
+                    self.seed_input.clear();
                 }
             }
-            false => {
+            ShowPopup::NoPopUp => {
                 if key.code == KeyCode::Up {
-                    if idx > 0 {
-                        idx -= 1;
-                    }
+                    idx = idx.saturating_sub(1);
                     self.menu_select_state.select(Some(idx));
                 }
                 if key.code == KeyCode::Down {
-                    if idx < 6 {
+                    if idx + 1 < tyrant_cards.len() {
                         idx += 1;
                     }
                     self.menu_select_state.select(Some(idx));
@@ -80,7 +131,7 @@ impl Component for SelectBossPage {
                 }
                 if key.code == KeyCode::Enter {
                     info!("[{}] Checking tyrant card info: {}", self.name, tyrant_card.name);
-                    self.is_popup = true;
+                    self.popup = ShowPopup::BossPopup;
                 }
             }
         }
@@ -91,31 +142,31 @@ impl Component for SelectBossPage {
     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> color_eyre::Result<()> {
         let mut rows: Vec<Row> = Vec::new();
         let mut boss_intro_list = Vec::new();
-        for card in TYRANT_CARDS.lock().unwrap().iter() {
-            let mut intro = card.battle_title.clone();
-            intro = intro + "\n------------------\n战斗机制：\n";
+        for card in self.library.tyrant_cards.iter() {
+            let mut intro = card.battle_title.to_string();
+            intro += tr!("\n------------------\n战斗机制：\n", "\n------------------\nBattle mechanism:\n").as_str();
             for m in card.battle_mechanism.iter() {
-                intro = intro + " *" + m.as_str() + "\n";
+                intro = intro + " *" + m.text() + "\n";
             }
-            intro = intro + "\n------------------\nBoss技能：\n";
+            intro += tr!("\n------------------\nBoss技能：\n", "\n------------------\nTyrant skills:\n").as_str();
             for s in card.tyrant_skills.iter() {
-                intro = intro + " *" + s.as_str() + "\n";
+                intro = intro + " *" + s.text() + "\n";
             }
-            intro = intro + "\n------------------\nBoss骰子：\n";
+            intro += tr!("\n------------------\nBoss骰子：\n", "\n------------------\nTyrant die:\n").as_str();
             for d in card.tyrant_die.iter() {
-                intro = intro + " *" + d.as_str() + "\n";
+                intro = intro + " *" + d.text() + "\n";
             }
 
             boss_intro_list.push(intro);
 
-            let desc = card.description.replace("。", "。\n");
+            let desc = card.description.text().replace("。", "。\n");
 
-            let r = Row::new(vec![card.name.clone(),
+            let r = Row::new(vec![card.name.to_string(),
                                   desc,
                                   card.game_length.clone(),
                                   card.min_progress.to_string(),
                                   card.max_days.to_string(),
-                                  card.creatures.clone()]).height(6);
+                                  card.creatures.to_string()]).height(6);
             rows.push(r);
         }
 
@@ -128,8 +179,8 @@ impl Component for SelectBossPage {
             Constraint::Length(40)
         ];
         let block = Block::default()
-            .title(Title::from(" 选择Boss ").alignment(Alignment::Center).position(Position::Top))
-            .title(Title::from(" <P> 键回退上一页 || <Enter> 键选择 || <Q> 键强制退出").alignment(Alignment::Center).position(Position::Bottom))
+            .title(Title::from(tr!(" 选择Boss ", " Select a tyrant ")).alignment(Alignment::Center).position(Position::Top))
+            .title(Title::from(tr!(" <P> 键回退上一页 || <Enter> 键选择 || <Q> 键强制退出", " <P> Back || <Enter> Select || <Q> Quit")).alignment(Alignment::Center).position(Position::Bottom))
             .borders(Borders::ALL)
             .padding(Padding::top(1))
             .border_set(border::THICK);
@@ -138,7 +189,8 @@ impl Component for SelectBossPage {
             .column_spacing(1)
             .style(Style::new().white())
             .header(
-                Row::new(vec!["Boss", "简介", "游戏时长", "最小挑战天数", "最大挑战天数", "怪物类型"])
+                Row::new(vec![tr!("Boss", "Tyrant"), tr!("简介", "Description"), tr!("游戏时长", "Game length"),
+                              tr!("最小挑战天数", "Min progress"), tr!("最大挑战天数", "Max days"), tr!("怪物类型", "Baddies")])
                     .style(Style::new().bold())
                     .bottom_margin(1),
             )
@@ -148,11 +200,31 @@ impl Component for SelectBossPage {
 
         f.render_stateful_widget(table, area, &mut self.menu_select_state);
 
-        if self.is_popup {
-            let popup_area = centered_rect(area, 60, 80);
-            let idx = self.menu_select_state.selected().unwrap();
-            let boss_info_popup = Popup::new(boss_intro_list[idx].clone(), " Boss介绍 ".to_string(), " <P> 键回退 || <Enter> 键选择Boss进行游戏 ".to_string());
-            f.render_widget(boss_info_popup, popup_area);
+        match self.popup {
+            ShowPopup::NoPopUp => {}
+            ShowPopup::BossPopup => {
+                let popup_area = centered_rect(area, 60, 80);
+                let idx = self.menu_select_state.selected().unwrap();
+                let seed = if self.seed_input.is_empty() { tr!("随机", "random") } else { self.seed_input.clone() };
+                let content = tr!("{}\n------------------\n种子：{}", "{}\n------------------\nSeed: {}", boss_intro_list[idx], seed);
+                let boss_info_popup = Popup::new(content, tr!(" Boss介绍 ", " Tyrant "),
+                                                 tr!(" <P> 键回退 || <0-9> 键输入种子 || <Enter> 键选择扩展 ", " <P> Back || <0-9> Type the seed || <Enter> Select the packs "));
+                f.render_widget(boss_info_popup, popup_area);
+            }
+            ShowPopup::PackPopup => {
+                let popup_area = centered_rect(area, 40, 50);
+                let mut content = tr!("选择使用的扩展。通用、特殊和Boss遭遇卡只从选中的扩展中抽取。\n\n",
+                                      "Select the content packs. The general, special and tyrant encounter cards are drawn from the selected packs only.\n\n");
+                for (i, (pack, selected)) in self.packs.iter().enumerate() {
+                    let key = std::char::from_u32('a' as u32 + i as u32).unwrap();
+                    content += format!("<{}> [{}] {}\n", key, if *selected { "x" } else { " " }, pack_name(pack)).as_str();
+                }
+                if self.packs.iter().all(|(_, selected)| !*selected) {
+                    content += tr!("\n至少选择一个扩展。", "\nSelect at least one pack.").as_str();
+                }
+                let pack_popup = Popup::new(content, tr!(" 选择扩展 ", " Content packs "), tr!(" <P> 键回退 || <Enter> 键选择队伍 ", " <P> Back || <Enter> Select the party "));
+                f.render_widget(pack_popup, popup_area);
+            }
         }
 
         Ok(())
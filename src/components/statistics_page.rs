@@ -0,0 +1,161 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::prelude::*;
+use ratatui::symbols::border;
+use ratatui::widgets::{Block, Borders, Padding, Row, Table};
+use ratatui::widgets::block::{Position, Title};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::info;
+use crate::app::Action;
+use crate::app::Action::Render;
+use crate::component::Component;
+use crate::components::home_page;
+use crate::core::history::{failed_encounters, load_history, tyrant_statistics, TyrantStatistics};
+use crate::core::i18n::tr;
+
+/// Statistics of the finished campaigns in the campaign history:
+/// - Win/loss, average days used versus the max days and average progress per tyrant.
+/// - The most failed encounter cards.
+///
+/// The history is reloaded every time the page receives an `Update` action.
+pub const NAME: &str = "StatisticsPage";
+
+const MAX_FAILED_ENCOUNTERS: usize = 10;
+
+pub struct StatisticsPage {
+    pub name: String,
+    pub action_sender: Option<UnboundedSender<Action>>,
+    tyrant_statistics: Vec<TyrantStatistics>,
+    failed_encounters: Vec<(String, String, usize)>,
+    error: Option<String>,
+}
+
+impl StatisticsPage {
+    pub fn new() -> Self {
+        StatisticsPage {
+            name: NAME.to_string(),
+            action_sender: None,
+            tyrant_statistics: Vec::new(),
+            failed_encounters: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn refresh(&mut self) {
+        match load_history() {
+            Ok(records) => {
+                info!("[{}] Loaded {} campaign records", self.name, records.len());
+                self.tyrant_statistics = tyrant_statistics(&records);
+                self.failed_encounters = failed_encounters(&records);
+                self.failed_encounters.truncate(MAX_FAILED_ENCOUNTERS);
+                self.error = None;
+            }
+            Err(e) => {
+                info!("[{}] Failed to load the campaign history: {}", self.name, e);
+                self.error = Some(tr!("无法读取历史记录：{}", "Failed to read the history: {}", e));
+            }
+        }
+    }
+}
+
+impl Component for StatisticsPage {
+    fn register_action_handler(&mut self, sender: UnboundedSender<Action>) -> color_eyre::Result<()> {
+        self.action_sender = Some(sender);
+        Ok(())
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> color_eyre::Result<()> {
+        if key.code == KeyCode::Char('p') {
+            self.action_sender.as_mut().unwrap().send(Render(home_page::NAME.to_string()))?;
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> color_eyre::Result<()> {
+        if let Action::Update(_, _) = action {
+            self.refresh();
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> color_eyre::Result<()> {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Percentage(50),
+                Constraint::Percentage(50),
+            ])
+            .split(area);
+
+        let mut tyrant_rows: Vec<Row> = Vec::new();
+        for s in self.tyrant_statistics.iter() {
+            let r = Row::new(vec![s.tyrant_name.clone(),
+                                  s.games.to_string(),
+                                  s.victories.to_string(),
+                                  s.defeats.to_string(),
+                                  format!("{:.0}%", s.victories as f64 * 100.0 / s.games as f64),
+                                  format!("{:.1} / {}", s.average_days, s.max_days),
+                                  format!("{:.1}", s.average_progress)]);
+            tyrant_rows.push(r);
+        }
+        if let Some(ref error) = self.error {
+            tyrant_rows.push(Row::new(vec![error.clone()]));
+        } else if tyrant_rows.is_empty() {
+            tyrant_rows.push(Row::new(vec![tr!("还没有完成的游戏", "No finished game yet")]));
+        }
+        let tyrant_widths = [
+            Constraint::Length(15),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(20),
+            Constraint::Length(15),
+        ];
+        let tyrant_block = Block::default()
+            .title(Title::from(tr!(" Boss统计 ", " Tyrants ")).alignment(Alignment::Center).position(Position::Top))
+            .borders(Borders::ALL)
+            .padding(Padding::top(1))
+            .border_set(border::THICK);
+        let tyrant_table = Table::new(tyrant_rows, tyrant_widths)
+            .column_spacing(1)
+            .style(Style::new().white())
+            .header(
+                Row::new(vec![tr!("Boss", "Tyrant"), tr!("场次", "Games"), tr!("胜利", "Victories"), tr!("失败", "Defeats"), tr!("胜率", "Win rate"),
+                              tr!("平均天数/最大天数", "Avg days/max days"), tr!("平均进度", "Avg progress")])
+                    .style(Style::new().bold())
+                    .bottom_margin(1),
+            )
+            .block(tyrant_block);
+
+        let mut encounter_rows: Vec<Row> = Vec::new();
+        for (id, title, count) in self.failed_encounters.iter() {
+            encounter_rows.push(Row::new(vec![title.clone(), id.clone(), count.to_string()]));
+        }
+        let encounter_widths = [
+            Constraint::Length(30),
+            Constraint::Length(30),
+            Constraint::Length(10),
+        ];
+        let encounter_block = Block::default()
+            .title(Title::from(tr!(" 失败最多的遭遇卡 ", " Most failed encounters ")).alignment(Alignment::Center).position(Position::Top))
+            .title(Title::from(tr!(" <P> 键回退上一页 || <Q> 键强制退出", " <P> Back || <Q> Quit")).alignment(Alignment::Center).position(Position::Bottom))
+            .borders(Borders::ALL)
+            .padding(Padding::top(1))
+            .border_set(border::THICK);
+        let encounter_table = Table::new(encounter_rows, encounter_widths)
+            .column_spacing(1)
+            .style(Style::new().white())
+            .header(
+                Row::new(vec![tr!("遭遇卡", "Encounter"), tr!("卡牌ID", "Card id"), tr!("失败次数", "Failures")])
+                    .style(Style::new().bold())
+                    .bottom_margin(1),
+            )
+            .block(encounter_block);
+
+        f.render_widget(tyrant_table, layout[0]);
+        f.render_widget(encounter_table, layout[1]);
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use crate::core::content::Card;
+use crate::core::i18n::{Language, LocalizedText};
+
+/// The baddies fought in the encounters. The database is read from `config/baddies`, one YAML file per baddie, like
+/// the cards. A baddie has a point value, a tier and the creature types of the tyrants it belongs to, e.g. "兽人".
+///
+/// Most encounter actions start with "BQ：反派点数": the Baddie Queue is filled with baddies worth the baddie points,
+/// the current day times the party size, adjusted by the card. `build_baddie_queue` draws the queue at random from the
+/// baddies of the creature types of the tyrant that fit the remaining points, up to the tier allowed for the day. The
+/// draw is seeded by the game seed, the day, the choice and the number of rerolls, so the same game gives the same queue.
+pub static BADDIE_PATH: &str = "/config/baddies";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Baddie {
+    #[serde(default)]
+    pub id: String, // The file name if not declared
+    pub name: LocalizedText,
+    pub points: usize,
+    pub tier: usize,
+    #[serde(default)]
+    pub tags: Vec<String>, // The creature types in Chinese, as in the `creatures` of the tyrant cards
+}
+
+impl Card for Baddie {
+    fn id_mut(&mut self) -> &mut String {
+        &mut self.id
+    }
+}
+
+/// The creature types of a tyrant card, e.g. "巨魔，鳞片怪，兽人".
+pub fn creature_types(creatures: &LocalizedText) -> Vec<String> {
+    creatures.get(Language::Zh).unwrap_or_default()
+        .split(['，', ','])
+        .map(|creature| creature.trim().to_string())
+        .filter(|creature| !creature.is_empty())
+        .collect()
+}
+
+/// The baddie points of a battle: the current day times the party size. A game without party counts as one Gearloc.
+pub fn baddie_points(day: usize, party_size: usize) -> usize {
+    day * party_size.max(1)
+}
+
+/// The seed of the Baddie Queue of a choice. Every reroll gives another queue.
+pub fn baddie_queue_seed(game_seed: u64, day: usize, choice: usize, rerolls: usize) -> u64 {
+    game_seed ^ ((day as u64) << 32 | (choice as u64) << 16 | rerolls as u64)
+}
+
+/// The highest tier of the baddies drawn on a day: tier 1 on days 1 to 3, tier 2 on days 4 to 6, tier 3 from day 7.
+pub fn max_tier(day: usize) -> usize {
+    day.div_ceil(3).max(1)
+}
+
+/// Draw the Baddie Queue, top first, worth the given points. Only the baddies of the creature types are drawn, or all
+/// the baddies if none matches, and only up to the tier of the day. Each draw takes a random baddie among those that
+/// still fit the remaining points, until none fits.
+pub fn build_baddie_queue(baddies: &[Baddie], points: usize, creature_types: &[String], day: usize, seed: u64) -> Vec<Baddie> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let tiered: Vec<&Baddie> = baddies.iter()
+        .filter(|baddie| baddie.points > 0 && baddie.tier <= max_tier(day))
+        .collect();
+    let matched: Vec<&Baddie> = tiered.iter().copied()
+        .filter(|baddie| baddie.tags.iter().any(|tag| creature_types.contains(tag)))
+        .collect();
+    let candidates = if matched.is_empty() { tiered } else { matched };
+    let mut queue = Vec::new();
+    let mut remaining = points;
+    loop {
+        let pool: Vec<&Baddie> = candidates.iter().copied().filter(|baddie| baddie.points <= remaining).collect();
+        if pool.is_empty() {
+            break;
+        }
+        let baddie = pool[rng.gen_range(0..pool.len())];
+        queue.push(baddie.clone());
+        remaining -= baddie.points;
+    }
+    queue
+}
+
+/// The Baddie Queue drawn on `GamePage` for a choice of today's encounter.
+#[derive(Debug, Clone, Default)]
+pub struct BaddieQueue {
+    pub choice: usize,
+    pub points: usize,
+    pub rerolls: usize,
+    pub baddies: Vec<Baddie>, // Top first
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn baddie(id: &str, points: usize, tier: usize, tag: &str) -> Baddie {
+        Baddie { id: id.to_string(), name: LocalizedText::default(), points, tier, tags: vec![tag.to_string()] }
+    }
+
+    #[test]
+    fn queue_fits_the_points_and_the_tier_of_the_day() {
+        let baddies = [baddie("grunt", 1, 1, "兽人"), baddie("berserker", 5, 2, "兽人"), baddie("warlord", 20, 3, "兽人"),
+            baddie("wolf", 1, 1, "狼")];
+        let orcs = ["兽人".to_string()];
+        for seed in 0..20 {
+            let queue = build_baddie_queue(&baddies, 12, &orcs, 2, seed);
+            assert!(queue.iter().all(|baddie| baddie.id == "grunt"));
+            assert_eq!(queue.len(), 12);
+            let queue = build_baddie_queue(&baddies, 25, &orcs, 7, seed);
+            assert!(queue.iter().all(|baddie| baddie.id != "wolf"));
+            assert_eq!(queue.iter().map(|baddie| baddie.points).sum::<usize>(), 25);
+        }
+        // The draw is random among the baddies that fit, not the highest value first
+        assert!((0..20).any(|seed| build_baddie_queue(&baddies, 10, &orcs, 4, seed)[0].id == "grunt"));
+        assert_eq!(ids(&build_baddie_queue(&baddies, 10, &orcs, 4, 3)), ids(&build_baddie_queue(&baddies, 10, &orcs, 4, 3)));
+    }
+
+    fn ids(queue: &[Baddie]) -> Vec<&str> {
+        queue.iter().map(|baddie| baddie.id.as_str()).collect()
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use color_eyre::eyre::{eyre, Result};
+use include_dir::{Dir, include_dir};
+use serde::de::DeserializeOwned;
+use crate::core::error::ContentError;
+
+/// The content directory is the directory that has the `config` directory of the cards. It is searched in this order,
+/// the first directory that has `config` wins:
+/// 1. The `--content-dir` flag. It must have `config`, otherwise the program stops.
+/// 2. The `TMB_CONTENT_DIR` environment variable.
+/// 3. `$XDG_DATA_HOME/tmb`, by default `~/.local/share/tmb`.
+/// 4. `tmb` in every directory of `$XDG_DATA_DIRS`, by default `/usr/local/share` and `/usr/share`.
+/// 5. The directory of the executable.
+/// 6. `CARGO_MANIFEST_DIR`, set by `cargo run`.
+/// 7. The working directory.
+///
+/// The resolution is done once at startup and kept for the diagnostics page.
+///
+/// The cards under `config` are also embedded into the binary as the built-in pack, so the program works without any
+/// content directory. The files of the content directory override the built-in cards with the same id in the same
+/// directory, i.e. the `id` declared in the card or the file name, and the other files are added to the built-in cards.
+/// `build.rs` makes cargo embed the cards again whenever `config` changes.
+pub static CONTENT_DIR_ENV: &str = "TMB_CONTENT_DIR";
+static CONTENT_DIR_NAME: &str = "tmb";
+static CONFIG_DIR: &str = "config";
+static BUILT_IN_DIR: &str = "<built-in>";
+static BUILT_IN_CONFIG: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/config");
+
+static CONTENT_DIR_RESOLUTION: OnceLock<ContentDirResolution> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentDirSource {
+    Flag,
+    Env,
+    XdgDataHome,
+    XdgDataDirs,
+    ExecutableDir,
+    ManifestDir,
+    WorkingDir,
+}
+
+impl ContentDirSource {
+    pub fn description(&self) -> &'static str {
+        match self {
+            ContentDirSource::Flag => "--content-dir",
+            ContentDirSource::Env => CONTENT_DIR_ENV,
+            ContentDirSource::XdgDataHome => "XDG_DATA_HOME",
+            ContentDirSource::XdgDataDirs => "XDG_DATA_DIRS",
+            ContentDirSource::ExecutableDir => "executable",
+            ContentDirSource::ManifestDir => "CARGO_MANIFEST_DIR",
+            ContentDirSource::WorkingDir => "working directory",
+        }
+    }
+}
+
+/// One searched directory and whether it has the `config` directory.
+#[derive(Debug, Clone)]
+pub struct ContentDirCandidate {
+    pub source: ContentDirSource,
+    pub path: PathBuf,
+    pub found: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentDirResolution {
+    pub candidates: Vec<ContentDirCandidate>,
+    pub content_dir: Option<PathBuf>, // Only the built-in cards are used if no content directory is found
+}
+
+/// A card file of the built-in pack or of the content directory.
+#[derive(Debug, Clone)]
+pub struct CardFile {
+    pub id: String, // The `id` declared in the card, or the file name without the extension
+    pub path: PathBuf, // `<built-in>/config/...` for the built-in cards
+    pub built_in: bool,
+    pub contents: String,
+}
+
+/// A kind of card read from one YAML file per card: the encounter cards, the tyrants, the Gearlocs, the baddies and the
+/// loot cards. All the kinds are loaded by `read_card` and `list_cards`, so they share the id rule: a card without a
+/// declared `id` takes the id of its file.
+pub trait Card: DeserializeOwned {
+    fn id_mut(&mut self) -> &mut String;
+
+    /// Fill the fields taken from the file once the id is set, e.g. the pack of an encounter card.
+    fn init(&mut self, _file: &CardFile) {}
+}
+
+pub fn read_card<T: Card>(file: &CardFile) -> Result<T, ContentError> {
+    let mut card: T = serde_yaml::from_str(&file.contents)
+        .map_err(|source| ContentError::Parse { path: file.path.clone(), source })?;
+    if card.id_mut().is_empty() {
+        *card.id_mut() = file.id.clone();
+    }
+    card.init(file);
+    Ok(card)
+}
+
+/// Read the cards of a directory under `config`, sorted by id. A card that fails to load is kept as its error.
+pub fn list_cards<T: Card>(dir_path: &str) -> Vec<Result<T, ContentError>> {
+    list_card_files(dir_path).into_iter()
+        .map(|file| file.and_then(|file| read_card(&file)))
+        .collect()
+}
+
+/// Resolve the content directory. Must be called once at startup before loading any card.
+pub fn init_content_dir(flag: Option<PathBuf>) -> Result<&'static ContentDirResolution> {
+    let resolution = resolve_content_dir(flag)?;
+    Ok(CONTENT_DIR_RESOLUTION.get_or_init(|| resolution))
+}
+
+/// The resolution of the content directory. Resolved without the flag if `init_content_dir` was not called.
+pub fn get_content_dir_resolution() -> &'static ContentDirResolution {
+    CONTENT_DIR_RESOLUTION.get_or_init(|| resolve_content_dir(None).unwrap_or_else(|_| ContentDirResolution {
+        candidates: Vec::new(),
+        content_dir: None,
+    }))
+}
+
+pub fn get_content_dir() -> Option<PathBuf> {
+    get_content_dir_resolution().content_dir.clone()
+}
+
+/// List the card files of a directory under `config`, e.g. "/config/encounters/day1", sorted by id.
+/// A file of the content directory replaces the built-in files with the same id. The files of one source with the same
+/// id are all kept, so `validate` can report them.
+pub fn list_card_files(dir_path: &str) -> Vec<Result<CardFile, ContentError>> {
+    list_card_files_in(get_content_dir().as_deref(), dir_path)
+}
+
+/// `list_card_files` over the given content directory, or the built-in cards only.
+fn list_card_files_in(content_dir: Option<&Path>, dir_path: &str) -> Vec<Result<CardFile, ContentError>> {
+    let mut files: BTreeMap<String, Vec<Result<CardFile, ContentError>>> = BTreeMap::new();
+    let relative_path = dir_path.trim_start_matches('/').trim_start_matches(CONFIG_DIR).trim_start_matches('/');
+    if let Some(dir) = BUILT_IN_CONFIG.get_dir(relative_path) {
+        for file in dir.files() {
+            let contents = file.contents_utf8().unwrap_or_default().to_string();
+            let id = card_id(file.path(), &contents);
+            let path = PathBuf::from(BUILT_IN_DIR).join(CONFIG_DIR).join(file.path());
+            files.entry(id.clone()).or_default().push(Ok(CardFile { id, path, built_in: true, contents }));
+        }
+    }
+    if let Some(content_dir) = content_dir {
+        let dir = content_dir.join(dir_path.trim_start_matches('/'));
+        if dir.is_dir() {
+            match std::fs::read_dir(&dir) {
+                Ok(entries) => {
+                    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).filter(|path| path.is_file()).collect();
+                    paths.sort();
+                    for path in paths {
+                        let (id, card_file) = match std::fs::read_to_string(&path) {
+                            Ok(contents) => {
+                                let id = card_id(&path, &contents);
+                                (id.clone(), Ok(CardFile { id, path, built_in: false, contents }))
+                            }
+                            Err(source) => (card_id(&path, ""), Err(ContentError::Io { path, source })),
+                        };
+                        let same_id = files.entry(id).or_default();
+                        if same_id.iter().all(|file| file.as_ref().is_ok_and(|file| file.built_in)) {
+                            same_id.clear();
+                        }
+                        same_id.push(card_file);
+                    }
+                }
+                Err(source) => return vec![Err(ContentError::Io { path: dir, source })],
+            }
+        }
+    }
+    files.into_values().flatten().collect()
+}
+
+/// The `id` declared in a card file, or the file name without the extension. A file that isn't valid YAML is listed by
+/// its file name, the card loader reports the error.
+fn card_id(path: &Path, contents: &str) -> String {
+    serde_yaml::from_str::<serde_yaml::Value>(contents).ok()
+        .and_then(|card| card.get("id").and_then(|id| id.as_str()).map(str::to_string))
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(|| path.file_stem().unwrap_or_default().to_string_lossy().to_string())
+}
+
+fn resolve_content_dir(flag: Option<PathBuf>) -> Result<ContentDirResolution> {
+    if let Some(ref path) = flag {
+        if !path.join(CONFIG_DIR).is_dir() {
+            return Err(eyre!("The content directory {:?} doesn't have the {} directory", path, CONFIG_DIR));
+        }
+    }
+    let candidates: Vec<ContentDirCandidate> = search_paths(flag).into_iter()
+        .map(|(source, path)| ContentDirCandidate { source, found: path.join(CONFIG_DIR).is_dir(), path })
+        .collect();
+    let content_dir = candidates.iter()
+        .find(|candidate| candidate.found)
+        .map(|candidate| candidate.path.clone());
+    Ok(ContentDirResolution { candidates, content_dir })
+}
+
+fn search_paths(flag: Option<PathBuf>) -> Vec<(ContentDirSource, PathBuf)> {
+    let mut paths = Vec::new();
+    if let Some(path) = flag {
+        paths.push((ContentDirSource::Flag, path));
+    }
+    if let Some(path) = non_empty_env(CONTENT_DIR_ENV) {
+        paths.push((ContentDirSource::Env, PathBuf::from(path)));
+    }
+    let data_home = non_empty_env("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty_env("HOME").map(|home| PathBuf::from(home).join(".local/share")));
+    if let Some(data_home) = data_home {
+        paths.push((ContentDirSource::XdgDataHome, data_home.join(CONTENT_DIR_NAME)));
+    }
+    let data_dirs = non_empty_env("XDG_DATA_DIRS").unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    for dir in data_dirs.split(':').filter(|dir| !dir.is_empty()) {
+        paths.push((ContentDirSource::XdgDataDirs, PathBuf::from(dir).join(CONTENT_DIR_NAME)));
+    }
+    if let Some(dir) = env::current_exe().ok().and_then(|exe| exe.parent().map(PathBuf::from)) {
+        paths.push((ContentDirSource::ExecutableDir, dir));
+    }
+    if let Some(dir) = non_empty_env("CARGO_MANIFEST_DIR") {
+        paths.push((ContentDirSource::ManifestDir, PathBuf::from(dir)));
+    }
+    paths.push((ContentDirSource::WorkingDir, PathBuf::from(".")));
+    paths
+}
+
+fn non_empty_env(key: &str) -> Option<String> {
+    env::var(key).ok().filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn external_card_overrides_the_built_in_card_by_its_declared_id() {
+        let content_dir = env::temp_dir().join(format!("tmb_content_test_{}", std::process::id()));
+        let gearloc_dir = content_dir.join("config/gearlocs");
+        std::fs::create_dir_all(&gearloc_dir).unwrap();
+        std::fs::write(gearloc_dir.join("my_boomer.yaml"), "id: boomer\nname: Boomer\nhealth: 9\n").unwrap();
+        std::fs::write(gearloc_dir.join("newcomer.yaml"), "name: Newcomer\nhealth: 3\n").unwrap();
+
+        let built_in: Vec<CardFile> = list_card_files_in(None, "/config/gearlocs").into_iter().map(Result::unwrap).collect();
+        let files: Vec<CardFile> = list_card_files_in(Some(&content_dir), "/config/gearlocs").into_iter().map(Result::unwrap).collect();
+        std::fs::remove_dir_all(&content_dir).unwrap();
+
+        assert!(built_in.iter().any(|file| file.id == "boomer"));
+        assert_eq!(files.len(), built_in.len() + 1);
+        let boomers: Vec<&CardFile> = files.iter().filter(|file| file.id == "boomer").collect();
+        assert_eq!(boomers.len(), 1);
+        assert!(!boomers[0].built_in);
+        assert_eq!(boomers[0].path, gearloc_dir.join("my_boomer.yaml"));
+        assert!(files.iter().any(|file| file.id == "newcomer" && !file.built_in));
+    }
+}
@@ -0,0 +1,101 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// The dice rolled during an encounter, e.g. "掷一个D6". The roller has its own RNG seeded by the game seed, apart from
+/// the RNG of the encounter deck, so rolling dice never changes the encounter sequence. The roller is kept in the saves.
+///
+/// The rolls of the day are recorded into the battle log of the day when the challenge is finished, so they can be
+/// reviewed on the battle log after the game or a rollback.
+pub const MAX_DICE: usize = 20;
+pub const MAX_SIDES: usize = 100;
+const DICE_SEED_SALT: u64 = 0xD1CE;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiceRoller {
+    rng: ChaCha8Rng,
+}
+
+/// One roll of some dice, or the health check of a Gearloc.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DiceRoll {
+    pub expression: String, // e.g. "2d6"
+    pub results: Vec<usize>,
+    #[serde(default)]
+    pub gearloc: Option<String>, // The Gearloc id of a health check
+    #[serde(default)]
+    pub passed: Option<bool>, // The result of a health check
+}
+
+impl DiceRoll {
+    pub fn total(&self) -> usize {
+        self.results.iter().sum()
+    }
+}
+
+impl DiceRoller {
+    pub fn new(game_seed: u64) -> Self {
+        DiceRoller { rng: ChaCha8Rng::seed_from_u64(game_seed ^ DICE_SEED_SALT) }
+    }
+
+    /// Roll the dice of an expression like "d6", "2d6" or "1D20". `None` if the expression isn't valid.
+    pub fn roll(&mut self, expression: &str) -> Option<DiceRoll> {
+        let (count, sides) = parse_dice(expression)?;
+        let results = (0..count).map(|_| self.rng.gen_range(1..=sides)).collect();
+        let roll = DiceRoll { expression: format!("{}d{}", count, sides), results, gearloc: None, passed: None };
+        info!("Rolled {}: {:?}", roll.expression, roll.results);
+        Some(roll)
+    }
+
+    /// The health check of a Gearloc: roll a D6, passed if the result is not higher than the current health.
+    pub fn health_check(&mut self, gearloc: &str, health: usize) -> DiceRoll {
+        let result = self.rng.gen_range(1..=6);
+        let roll = DiceRoll {
+            expression: "1d6".to_string(),
+            results: vec![result],
+            gearloc: Some(gearloc.to_string()),
+            passed: Some(result <= health),
+        };
+        info!("Health check of {} with {} health: rolled {}", gearloc, health, result);
+        roll
+    }
+}
+
+/// Parse "NdX" into the number of dice and the sides. N defaults to 1. Both are limited to keep the rolls readable.
+pub fn parse_dice(expression: &str) -> Option<(usize, usize)> {
+    let expression = expression.trim().to_lowercase();
+    let (count, sides) = expression.split_once('d')?;
+    let count = if count.is_empty() { 1 } else { count.parse().ok()? };
+    let sides: usize = sides.parse().ok()?;
+    ((1..=MAX_DICE).contains(&count) && (2..=MAX_SIDES).contains(&sides)).then_some((count, sides))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_dice_reads_the_count_and_the_sides() {
+        assert_eq!(parse_dice("d6"), Some((1, 6)));
+        assert_eq!(parse_dice("2d6"), Some((2, 6)));
+        assert_eq!(parse_dice(" 1D20 "), Some((1, 20)));
+        assert_eq!(parse_dice("0d6"), None);
+        assert_eq!(parse_dice("21d6"), None);
+        assert_eq!(parse_dice("d1"), None);
+        assert_eq!(parse_dice("d101"), None);
+        assert_eq!(parse_dice("6"), None);
+        assert_eq!(parse_dice("xd6"), None);
+    }
+
+    #[test]
+    fn same_seed_rolls_the_same_dice() {
+        let mut roller1 = DiceRoller::new(7);
+        let mut roller2 = DiceRoller::new(7);
+        let roll = roller1.roll("3d6").unwrap();
+        assert_eq!(roll, roller2.roll("3d6").unwrap());
+        assert_eq!(roll.results.len(), 3);
+        assert!(roll.results.iter().all(|result| (1..=6).contains(result)));
+        assert!(roller1.roll("d0").is_none());
+    }
+}
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Errors of loading the cards under `config`. A broken card is skipped and its error is kept in the `CardLibrary`,
+/// so the home page can list what failed to load instead of the program exiting.
+#[derive(Debug, Error)]
+pub enum ContentError {
+    #[error("Failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to parse {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+    #[error("Unknown card_type \"{0}\", expected general, day1, day2, day3, special or tyrant:<tyrant id>")]
+    UnknownCardType(String),
+    #[error("Tyrant {0} doesn't exist")]
+    UnknownTyrant(String),
+    #[error("Tyrant {tyrant_id} has {max_days} max days, not enough for {tyrant_encounters} tyrant encounter cards plus 3 day cards")]
+    NotEnoughDays {
+        tyrant_id: String,
+        max_days: usize,
+        tyrant_encounters: usize,
+    },
+    #[error("Not enough {card_type} encounter cards: {required} required, {available} available")]
+    NotEnoughCards {
+        card_type: &'static str,
+        required: usize,
+        available: usize,
+    },
+}
+
+/// Errors of reading and writing the files of the players: the saves, the settings, the campaign history and the
+/// exported battle logs. They are shown on the pages or logged, and never end the session.
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Yaml(#[from] serde_yaml::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
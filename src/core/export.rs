@@ -0,0 +1,145 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use chrono::Local;
+use clap::ValueEnum;
+use serde::Serialize;
+use tracing::info;
+use crate::core::game::BattleLog;
+use crate::core::i18n::tr;
+use crate::core::error::StorageError;
+use crate::utils::get_data_dir;
+
+/// Export the battle log of a campaign for the session recaps. Every row has the day, the encounter title,
+/// the chosen choice, the progress, the rewards (only for successful challenges) and whether the challenge succeeded.
+/// CSV and JSON also have the card id, to match the rows with the card files.
+static EXPORT_DIR: &str = "exports";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Markdown,
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ExportedLog<'a> {
+    day: usize,
+    card_id: &'a str,
+    title: &'a str,
+    choice: &'a str,
+    action: &'a str,
+    progress: usize,
+    rewards: &'a str,
+    success: bool,
+}
+
+impl<'a> From<&'a BattleLog> for ExportedLog<'a> {
+    fn from(log: &'a BattleLog) -> Self {
+        ExportedLog {
+            day: log.day,
+            card_id: log.card_id.as_str(),
+            title: log.title.text(),
+            choice: log.choice.description.text(),
+            action: log.choice.action.text(),
+            progress: log.progress,
+            rewards: if log.success { log.choice.rewards.text() } else { "" },
+            success: log.success,
+        }
+    }
+}
+
+/// Render the battle log in the given format.
+pub fn render_battle_logs(tyrant_name: &str, battle_logs: &[BattleLog], format: ExportFormat) -> Result<String, StorageError> {
+    let logs: Vec<ExportedLog> = battle_logs.iter().map(ExportedLog::from).collect();
+    let content = match format {
+        ExportFormat::Markdown => {
+            let mut content = format!("# {}\n\n", tyrant_name);
+            content += tr!("| 天数 | 战斗 | 选择 | 进度 | 奖励 | 是否成功 |\n", "| Day | Battle | Choice | Progress | Rewards | Success |\n").as_str();
+            content += "| --- | --- | --- | --- | --- | --- |\n";
+            for log in logs {
+                content += format!("| {} | {} | {} | {} | {} | {} |\n",
+                                   log.day,
+                                   escape_markdown(log.title),
+                                   escape_markdown(log.choice),
+                                   log.progress,
+                                   escape_markdown(log.rewards),
+                                   if log.success { "✅" } else { "❌" }).as_str();
+            }
+            content
+        }
+        ExportFormat::Csv => {
+            let mut content = "day,card_id,title,choice,progress,rewards,success\n".to_string();
+            for log in logs {
+                content += format!("{},{},{},{},{},{},{}\n",
+                                   log.day,
+                                   escape_csv(log.card_id),
+                                   escape_csv(log.title),
+                                   escape_csv(log.choice),
+                                   log.progress,
+                                   escape_csv(log.rewards),
+                                   log.success).as_str();
+            }
+            content
+        }
+        ExportFormat::Json => serde_json::to_string_pretty(&logs)?,
+    };
+    Ok(content)
+}
+
+/// Write the battle log into a new file of the export directory. Returns the path of the file.
+pub fn export_battle_logs(tyrant_name: &str, battle_logs: &[BattleLog], format: ExportFormat) -> Result<PathBuf, StorageError> {
+    let directory = get_export_dir();
+    std::fs::create_dir_all(&directory)?;
+    let file_name = format!("battle_log_{}.{}", Local::now().format("%Y%m%d%H%M%S"), format.extension());
+    let file_path = directory.join(file_name);
+    write_battle_logs(tyrant_name, battle_logs, format, &file_path)?;
+    Ok(file_path)
+}
+
+/// Write the battle log into the given file.
+pub fn write_battle_logs(tyrant_name: &str, battle_logs: &[BattleLog], format: ExportFormat, file_path: &Path) -> Result<(), StorageError> {
+    let content = render_battle_logs(tyrant_name, battle_logs, format)?;
+    File::create(file_path)?.write_all(content.as_bytes())?;
+    info!("Exported the battle log to {:?}", file_path);
+    Ok(())
+}
+
+pub fn get_export_dir() -> PathBuf {
+    get_data_dir().join(EXPORT_DIR)
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn escape_csv(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_csv_quotes_only_when_needed() {
+        assert_eq!(escape_csv("plain text"), "plain text");
+        assert_eq!(escape_csv("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv("two\nlines"), "\"two\nlines\"");
+    }
+}
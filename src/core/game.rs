@@ -1,29 +1,35 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::Read;
-use std::path::PathBuf;
-use std::sync::Mutex;
-use lazy_static::lazy_static;
-use rand::{Rng, thread_rng};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use rand::{Rng, SeedableRng, thread_rng};
 use rand::prelude::SliceRandom;
-use serde::Deserialize;
-use tracing::info;
-use crate::utils::get_project_root_path;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use crate::core::error::ContentError;
+use crate::core::content::{Card, CardFile, list_card_files, list_cards};
+use crate::core::i18n::{language, Language, LocalizedText};
+use crate::core::party::{Gearloc, GEARLOC_PATH};
+use crate::core::baddie::{Baddie, BADDIE_PATH};
+use crate::core::dice::DiceRoll;
+use crate::core::loot::{LootCard, LootDraw, LOOT_PATH, TROVE_LOOT_PATH};
 
-/// Some global variables about some basic game information:
+/// Some basic game information:
 /// 1. Encounter cards.
 /// 2. Tyrant cards.
 /// 3. The encounter deck built during runtime.
 ///
-/// These global card deck maintain just one copy and should be globally used.
-/// `EncounterDeck` has basic deck build strategies and shuffle strategies.
+/// All the cards are loaded once into a `CardLibrary`, which is shared by the pages and never modified.
+/// Every game clones the library into its own `CardPool`, and the encounter deck draws the cards out of that pool.
+/// `EncounterDeck` has basic deck build strategies and shuffle strategies. All the randomness of a game comes from the
+/// seeded RNG stored on the deck, so the same seed and the same choices always give the same encounter sequence.
 static ENCOUNTER_CARD_GENERAL_PATH: &str = "/config/encounters/general";
 static ENCOUNTER_CARD_DAY1_PATH: &str = "/config/encounters/day1";
 static ENCOUNTER_CARD_DAY2_PATH: &str = "/config/encounters/day2";
 static ENCOUNTER_CARD_DAY3_PATH: &str = "/config/encounters/day3";
 static ENCOUNTER_CARD_SPECIAL_PATH: &str = "/config/encounters/special";
 static ENCOUNTER_CARD_TYRANT_PATH: &str = "/config/encounters/tyrant";
-static TYRANT_CARD_GENERAL_PATH: &str = "/config/tyrants";
+pub static TYRANT_CARD_GENERAL_PATH: &str = "/config/tyrants";
 
 pub static TYRANT_NAME_DUSTER: &str = "Duster";
 pub static TYRANT_NAME_NOM: &str = "Nom";
@@ -32,214 +38,525 @@ pub static TYRANT_NAME_MULMESH: &str = "Mulmesh";
 pub static TYRANT_NAME_GENDRICKS: &str = "Gendricks";
 pub static TYRANT_NAME_GOBLIN_KING: &str = "Goblin_King";
 pub static TYRANT_NAME_MARROW: &str = "Marrow";
+/// The content packs known by name. The pack of a card is the prefix of its file name, e.g. `40days_general_011.yaml`
+/// is in the pack "40days". Unknown prefixes are packs too, named by the prefix. The names are in Chinese and English.
+pub static CONTENT_PACKS: [(&str, &str, &str); 4] = [
+    ("base_set", "基础版", "Base Set"),
+    ("40days", "40天", "40 Days in Daelore"),
+    ("undertow", "暗流", "Undertow"),
+    ("unbreakable", "坚不可摧", "Unbreakable"),
+];
+pub static TYRANT_NAMES: [&str; 7] = [TYRANT_NAME_DUSTER, TYRANT_NAME_NOM, TYRANT_NAME_GENDRICKS, TYRANT_NAME_DRELLEN, TYRANT_NAME_MARROW, TYRANT_NAME_GOBLIN_KING, TYRANT_NAME_MULMESH];
 
-lazy_static! {
-    pub static ref DAY1_ENCOUNTER_CARDS: Mutex<Vec<EncounterCard>> = Mutex::new(list_day1_encounter_cards());
-    pub static ref DAY2_ENCOUNTER_CARDS: Mutex<Vec<EncounterCard>> = Mutex::new(list_day2_encounter_cards());
-    pub static ref DAY3_ENCOUNTER_CARDS: Mutex<Vec<EncounterCard>> = Mutex::new(list_day3_encounter_cards());
-    pub static ref SPECIAL_ENCOUNTER_CARDS: Mutex<Vec<EncounterCard>> = Mutex::new(list_special_encounter_cards());
-    pub static ref GENERAL_ENCOUNTER_CARDS: Mutex<Vec<EncounterCard>> = Mutex::new(list_general_encounter_cards());
-    pub static ref TYRANT_ENCOUNTER_CARDS: Mutex<HashMap<String, Vec<EncounterCard>>> = Mutex::new(list_tyrant_encounter_cards());
-    pub static ref TYRANT_CARDS: Mutex<Vec<TyrantCard>> = Mutex::new(list_tyrant_cards());
+/// Every card of the game. Loaded once at startup and shared read-only.
+/// The cards that failed to load are skipped, their errors are kept in `errors`.
+#[derive(Debug)]
+pub struct CardLibrary {
+    pub day1_encounter_cards: Vec<EncounterCard>,
+    pub day2_encounter_cards: Vec<EncounterCard>,
+    pub day3_encounter_cards: Vec<EncounterCard>,
+    pub special_encounter_cards: Vec<EncounterCard>,
+    pub general_encounter_cards: Vec<EncounterCard>,
+    pub tyrant_encounter_cards: HashMap<String, Vec<EncounterCard>>,
+    pub tyrant_cards: Vec<TyrantCard>,
+    pub gearlocs: Vec<Gearloc>,
+    pub baddies: Vec<Baddie>,
+    pub loot_cards: Vec<LootCard>,
+    pub trove_loot_cards: Vec<LootCard>,
+    pub errors: Vec<ContentError>,
+}
+
+/// The cards still available to one game. Cards are removed from the pool once they are drawn into the encounter deck.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CardPool {
+    pub day1_encounter_cards: Vec<EncounterCard>,
+    pub day2_encounter_cards: Vec<EncounterCard>,
+    pub day3_encounter_cards: Vec<EncounterCard>,
+    pub special_encounter_cards: Vec<EncounterCard>,
+    pub general_encounter_cards: Vec<EncounterCard>,
+    pub tyrant_encounter_cards: HashMap<String, Vec<EncounterCard>>,
+}
+
+impl CardLibrary {
+    pub fn load() -> Self {
+        let mut errors = Vec::new();
+        let mut tyrant_encounter_cards = HashMap::new();
+        for (name, cards) in list_tyrant_encounter_cards() {
+            tyrant_encounter_cards.insert(name, skip_errors(cards, &mut errors));
+        }
+        let library = CardLibrary {
+            day1_encounter_cards: skip_errors(list_day1_encounter_cards(), &mut errors),
+            day2_encounter_cards: skip_errors(list_day2_encounter_cards(), &mut errors),
+            day3_encounter_cards: skip_errors(list_day3_encounter_cards(), &mut errors),
+            special_encounter_cards: skip_errors(list_special_encounter_cards(), &mut errors),
+            general_encounter_cards: skip_errors(list_general_encounter_cards(), &mut errors),
+            tyrant_encounter_cards,
+            tyrant_cards: skip_errors(list_cards(TYRANT_CARD_GENERAL_PATH), &mut errors),
+            gearlocs: skip_errors(list_cards(GEARLOC_PATH), &mut errors),
+            baddies: skip_errors(list_cards(BADDIE_PATH), &mut errors),
+            loot_cards: skip_errors(list_cards(LOOT_PATH), &mut errors),
+            trove_loot_cards: skip_errors(list_cards(TROVE_LOOT_PATH), &mut errors),
+            errors,
+        };
+        info!("Loaded card library: {} general, {} special, {} tyrant cards, {} gearlocs, {} baddies, {} errors",
+            library.general_encounter_cards.len(), library.special_encounter_cards.len(), library.tyrant_cards.len(), library.gearlocs.len(), library.baddies.len(), library.errors.len());
+        library
+    }
+
+    pub fn tyrant_card(&self, id: &str) -> Option<&TyrantCard> {
+        self.tyrant_cards.iter().find(|card| card.id == id)
+    }
+
+    /// A loot or trove loot card by id.
+    pub fn loot_card(&self, id: &str) -> Option<&LootCard> {
+        self.loot_cards.iter().chain(self.trove_loot_cards.iter()).find(|card| card.id == id)
+    }
+
+    pub fn gearloc(&self, id: &str) -> Option<&Gearloc> {
+        self.gearlocs.iter().find(|gearloc| gearloc.id == id)
+    }
+
+    /// The names of the Gearlocs of a party, for display. An unknown Gearloc is shown by its id.
+    pub fn party_names(&self, party: &[String]) -> Vec<String> {
+        party.iter()
+            .map(|id| self.gearloc(id).map(|gearloc| gearloc.name.to_string()).unwrap_or_else(|| id.clone()))
+            .collect()
+    }
+
+    /// The content packs of the loaded encounter cards, the known packs first.
+    pub fn packs(&self) -> Vec<String> {
+        let mut packs: Vec<String> = Vec::new();
+        let cards = self.general_encounter_cards.iter()
+            .chain(self.special_encounter_cards.iter())
+            .chain(self.day1_encounter_cards.iter())
+            .chain(self.day2_encounter_cards.iter())
+            .chain(self.day3_encounter_cards.iter())
+            .chain(self.tyrant_encounter_cards.values().flatten());
+        for card in cards {
+            if !packs.contains(&card.pack) {
+                packs.push(card.pack.clone());
+            }
+        }
+        packs.sort_by_key(|pack| (CONTENT_PACKS.iter().position(|(id, _, _)| id == pack).unwrap_or(CONTENT_PACKS.len()), pack.clone()));
+        packs
+    }
+
+    /// A new card pool with the general, special and tyrant encounter cards of the given packs. Empty means all packs.
+    /// The day cards are never filtered.
+    pub fn new_pool(&self, packs: &[String]) -> CardPool {
+        let filter = |cards: &Vec<EncounterCard>| -> Vec<EncounterCard> {
+            cards.iter().filter(|card| packs.is_empty() || packs.contains(&card.pack)).cloned().collect()
+        };
+        CardPool {
+            day1_encounter_cards: self.day1_encounter_cards.clone(),
+            day2_encounter_cards: self.day2_encounter_cards.clone(),
+            day3_encounter_cards: self.day3_encounter_cards.clone(),
+            special_encounter_cards: filter(&self.special_encounter_cards),
+            general_encounter_cards: filter(&self.general_encounter_cards),
+            tyrant_encounter_cards: self.tyrant_encounter_cards.iter().map(|(name, cards)| (name.clone(), filter(cards))).collect(),
+        }
+    }
+
+    /// Fill the ids of the cards read from a save made before the cards had ids, by matching the title and the card
+    /// type with the loaded cards. The cards without a match keep the empty id.
+    pub fn fill_card_ids<'a>(&self, cards: impl Iterator<Item = &'a mut EncounterCard>) {
+        let loaded: Vec<&EncounterCard> = self.general_encounter_cards.iter()
+            .chain(self.special_encounter_cards.iter())
+            .chain(self.day1_encounter_cards.iter())
+            .chain(self.day2_encounter_cards.iter())
+            .chain(self.day3_encounter_cards.iter())
+            .chain(self.tyrant_encounter_cards.values().flatten())
+            .collect();
+        for card in cards.filter(|card| card.id.is_empty()) {
+            if let Some(found) = loaded.iter().find(|found| found.title.get(Language::Zh) == card.title.get(Language::Zh) && found.card_type.directory() == card.card_type.directory()) {
+                card.id = found.id.clone();
+                card.card_type = found.card_type.clone();
+                card.pack = found.pack.clone();
+                card.source = found.source.clone();
+            }
+        }
+    }
+}
+
+/// Keep the loaded cards, move the errors into `errors`.
+fn skip_errors<T>(results: Vec<Result<T, ContentError>>, errors: &mut Vec<ContentError>) -> Vec<T> {
+    let mut cards = Vec::new();
+    for result in results {
+        match result {
+            Ok(card) => cards.push(card),
+            Err(e) => {
+                warn!("Skipped a card: {}", e);
+                errors.push(e);
+            }
+        }
+    }
+    cards
 }
 
-pub fn list_day1_encounter_cards() -> Vec<EncounterCard> {
-    list_encounter_cards(get_project_root_path() + ENCOUNTER_CARD_DAY1_PATH, None)
+/// The card files are sorted by id. `read_dir` doesn't guarantee any order, but a seeded game needs the card pools in
+/// the same order on every machine.
+pub fn list_day1_encounter_cards() -> Vec<Result<EncounterCard, ContentError>> {
+    list_cards(ENCOUNTER_CARD_DAY1_PATH)
 }
 
-pub fn list_day2_encounter_cards() -> Vec<EncounterCard> {
-    list_encounter_cards(get_project_root_path() + ENCOUNTER_CARD_DAY2_PATH, None)
+pub fn list_day2_encounter_cards() -> Vec<Result<EncounterCard, ContentError>> {
+    list_cards(ENCOUNTER_CARD_DAY2_PATH)
 }
 
-pub fn list_day3_encounter_cards() -> Vec<EncounterCard> {
-    list_encounter_cards(get_project_root_path() + ENCOUNTER_CARD_DAY3_PATH, None)
+pub fn list_day3_encounter_cards() -> Vec<Result<EncounterCard, ContentError>> {
+    list_cards(ENCOUNTER_CARD_DAY3_PATH)
 }
 
-pub fn list_general_encounter_cards() -> Vec<EncounterCard> {
-    list_encounter_cards(get_project_root_path() + ENCOUNTER_CARD_GENERAL_PATH, None)
+pub fn list_general_encounter_cards() -> Vec<Result<EncounterCard, ContentError>> {
+    list_cards(ENCOUNTER_CARD_GENERAL_PATH)
 }
 
-pub fn list_special_encounter_cards() -> Vec<EncounterCard> {
-    list_encounter_cards(get_project_root_path() + ENCOUNTER_CARD_SPECIAL_PATH, None)
+pub fn list_special_encounter_cards() -> Vec<Result<EncounterCard, ContentError>> {
+    list_cards(ENCOUNTER_CARD_SPECIAL_PATH)
 }
 
-pub fn list_tyrant_encounter_cards() -> HashMap<String, Vec<EncounterCard>> {
-    let tyrant_names = [TYRANT_NAME_DUSTER, TYRANT_NAME_NOM, TYRANT_NAME_GENDRICKS, TYRANT_NAME_DRELLEN, TYRANT_NAME_MARROW, TYRANT_NAME_GOBLIN_KING, TYRANT_NAME_MULMESH];
-    let mut map = HashMap::new();
-    for name in tyrant_names {
-        map.insert(name.to_lowercase(), list_encounter_cards(get_project_root_path() + ENCOUNTER_CARD_TYRANT_PATH, Some(name.to_lowercase())));
+/// The tyrant encounter cards grouped by the tyrant of their `card_type`. The cards that fail to load are grouped under
+/// the empty id, so their errors are still collected.
+pub fn list_tyrant_encounter_cards() -> HashMap<String, Vec<Result<EncounterCard, ContentError>>> {
+    let mut map: HashMap<String, Vec<Result<EncounterCard, ContentError>>> = TYRANT_NAMES.iter()
+        .map(|name| (name.to_lowercase(), Vec::new()))
+        .collect();
+    for card in list_cards::<EncounterCard>(ENCOUNTER_CARD_TYRANT_PATH) {
+        let tyrant_id = match &card {
+            Ok(EncounterCard { card_type: CardType::Tyrant(tyrant_id), .. }) => tyrant_id.clone(),
+            _ => String::new(),
+        };
+        map.entry(tyrant_id).or_default().push(card);
     }
     map
 }
 
-pub fn list_tyrant_cards() -> Vec<TyrantCard> {
-    let dir_path = get_project_root_path() + TYRANT_CARD_GENERAL_PATH;
-    let mut tyrant_cards: Vec<TyrantCard> = Vec::new();
-    for file in std::fs::read_dir(PathBuf::from(dir_path.as_str())).expect(format!("Failed to open directory {}", dir_path.as_str()).as_str()) {
-        let path = file.unwrap().path();
-        if path.is_file() {
-            let card = TyrantCard::new(path.to_str().unwrap());
-            match card {
-                Some(c) => tyrant_cards.push(c),
-                _ => {}
-            }
+/// Every encounter card file with its directory name, e.g. `("day1", file)`. The id of the file is used as the card id
+/// on the command line.
+pub fn list_encounter_card_files() -> Vec<(String, Result<CardFile, ContentError>)> {
+    let mut files = Vec::new();
+    for dir in [ENCOUNTER_CARD_GENERAL_PATH, ENCOUNTER_CARD_DAY1_PATH, ENCOUNTER_CARD_DAY2_PATH, ENCOUNTER_CARD_DAY3_PATH,
+        ENCOUNTER_CARD_SPECIAL_PATH, ENCOUNTER_CARD_TYRANT_PATH] {
+        let category = dir.rsplit('/').next().unwrap_or_default().to_string();
+        for file in list_card_files(dir) {
+            files.push((category.clone(), file));
         }
     }
-    tyrant_cards
+    files
 }
 
-fn list_encounter_cards(dir_path: String, file_name_filter: Option<String>) -> Vec<EncounterCard> {
-    let mut encounter_cards: Vec<EncounterCard> = Vec::new();
-    for file in std::fs::read_dir(PathBuf::from(dir_path.as_str())).expect(format!("Failed to open directory {}", dir_path.as_str()).as_str()) {
-        let path = file.unwrap().path();
-        if path.is_file() {
-            if let Some(ref filter) = file_name_filter {
-                if path.file_name().unwrap().to_string_lossy().to_string().contains(filter) {
-                    let card = EncounterCard::new(path.to_str().unwrap());
-                    match card {
-                        Some(c) => encounter_cards.push(c),
-                        _ => {}
-                    }
-                }
-            } else {
-                let card = EncounterCard::new(path.to_str().unwrap());
-                match card {
-                    Some(c) => encounter_cards.push(c),
-                    _ => {}
-                }
-            }
-        }
-    }
-    encounter_cards
+/// The content pack of a card file id: the longest known pack that prefixes the id, or the text before the first `_`.
+pub fn pack_of(id: &str) -> String {
+    CONTENT_PACKS.iter()
+        .map(|(pack, _, _)| *pack)
+        .filter(|pack| id.starts_with(format!("{}_", pack).as_str()))
+        .max_by_key(|pack| pack.len())
+        .unwrap_or_else(|| id.split('_').next().unwrap_or_default())
+        .to_string()
 }
 
-#[derive(Debug, Deserialize, Clone)]
+/// The display name of a content pack in the current language.
+pub fn pack_name(pack: &str) -> String {
+    CONTENT_PACKS.iter()
+        .find(|(id, _, _)| *id == pack)
+        .map(|(_, zh, en)| match language() {
+            Language::Zh => zh.to_string(),
+            Language::En => en.to_string(),
+        })
+        .unwrap_or_else(|| pack.to_string())
+}
+
+/// An encounter card. The id is declared in the YAML or is the file name, e.g. "base_set_general_017", and is unique
+/// across all the packs, unlike the title.
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EncounterCard {
-    pub title: String,
-    pub story: String,
+    #[serde(default)]
+    pub id: String,
+    pub title: LocalizedText,
+    pub story: LocalizedText,
     pub choices: Vec<Choice>,
-    pub remark: String,
-    pub progress: Vec<usize>,
-    pub card_type: String,
+    pub remark: LocalizedText,
+    #[serde(default)]
+    pub progress: Vec<usize>, // The progress of each choice, unless given by the effects of the choice
+    pub card_type: CardType,
+    #[serde(default)]
+    pub pack: String, // Declared in the YAML or the prefix of the id
+    #[serde(default)]
+    pub source: String, // The file the card is loaded from
+}
+
+/// The type of an encounter card, written as "general", "day1", "special" or "tyrant:nom" in the YAML. A plain "tyrant"
+/// is accepted for the old cards and saves, its tyrant is then taken from the card id.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub enum CardType {
+    General,
+    Day(usize),
+    Special,
+    Tyrant(String),
+}
+
+impl CardType {
+    /// The directory of the cards of this type under "/config/encounters".
+    pub fn directory(&self) -> String {
+        match self {
+            CardType::General => "general".to_string(),
+            CardType::Day(day) => format!("day{}", day),
+            CardType::Special => "special".to_string(),
+            CardType::Tyrant(_) => "tyrant".to_string(),
+        }
+    }
+}
+
+impl Display for CardType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardType::Tyrant(tyrant_id) if !tyrant_id.is_empty() => write!(f, "tyrant:{}", tyrant_id),
+            _ => write!(f, "{}", self.directory()),
+        }
+    }
+}
+
+impl FromStr for CardType {
+    type Err = ContentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let day = s.strip_prefix("day").and_then(|day| day.parse::<usize>().ok()).filter(|day| *day > 0);
+        match (s, day, s.strip_prefix("tyrant:")) {
+            ("general", _, _) => Ok(CardType::General),
+            ("special", _, _) => Ok(CardType::Special),
+            ("tyrant", _, _) => Ok(CardType::Tyrant(String::new())),
+            (_, Some(day), _) => Ok(CardType::Day(day)),
+            (_, _, Some(tyrant_id)) if !tyrant_id.is_empty() => Ok(CardType::Tyrant(tyrant_id.to_string())),
+            _ => Err(ContentError::UnknownCardType(s.to_string())),
+        }
+    }
+}
+
+impl TryFrom<String> for CardType {
+    type Error = ContentError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<CardType> for String {
+    fn from(card_type: CardType) -> Self {
+        card_type.to_string()
+    }
+}
+
+/// The tyrant whose id is in the card id, e.g. "nom" for "base_set_nom_001".
+pub fn tyrant_of(card_id: &str) -> Option<String> {
+    TYRANT_NAMES.iter().map(|name| name.to_lowercase()).find(|tyrant_id| card_id.contains(tyrant_id.as_str()))
 }
 
-#[derive(Debug, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Choice {
-    pub description: String,
-    pub action: String,
-    pub rewards: String,
+    pub description: LocalizedText,
+    pub action: LocalizedText,
+    pub rewards: LocalizedText,
+    #[serde(default, skip_serializing_if = "Effects::is_empty")]
+    pub effects: Effects,
+}
+
+/// The structured effects of a choice, applied by `GamePage`. All the fields are optional, the action and the rewards
+/// text stay the rules read by the players:
+/// - `baddie_points`: the extra baddie points of the BQ for a party of 1, 2, 3 and 4 Gearlocs, e.g. `[4, 4, 8, 8]`.
+/// - `progress`, `skill_points`, `loot` and `trove_loot`: the rewards of a successful challenge. The skill points, loot
+///   and trove loot are given to each Gearloc.
+/// - `health`: the health change of each Gearloc when the challenge is taken, negative for damage.
+/// - `draw_another_encounter`: today's encounter card is shuffled back and another one is drawn for today.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct Effects {
+    pub baddie_points: Vec<i32>,
+    pub progress: Option<usize>,
+    pub skill_points: Option<usize>,
+    pub loot: usize,
+    pub trove_loot: usize,
+    pub health: i32,
+    pub draw_another_encounter: bool,
 }
 
-#[derive(Debug, Deserialize)]
+impl Effects {
+    pub fn is_empty(&self) -> bool {
+        *self == Effects::default()
+    }
+
+    /// The extra baddie points for the party size. The last value covers the bigger parties.
+    pub fn extra_baddie_points(&self, party_size: usize) -> i32 {
+        let idx = party_size.max(1) - 1;
+        self.baddie_points.get(idx).or(self.baddie_points.last()).copied().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TyrantCard {
     pub id: String,
-    pub name: String,
-    pub description: String,
+    pub name: LocalizedText,
+    pub description: LocalizedText,
     pub min_progress: usize,
     pub max_days: usize,
     pub game_length: String,
-    pub battle_title: String,
-    pub creatures: String,
-    pub battle_mechanism: Vec<String>,
-    pub tyrant_skills: Vec<String>,
-    pub tyrant_die: Vec<String>,
+    pub battle_title: LocalizedText,
+    pub creatures: LocalizedText,
+    pub battle_mechanism: Vec<LocalizedText>,
+    pub tyrant_skills: Vec<LocalizedText>,
+    pub tyrant_die: Vec<LocalizedText>,
 }
 
 impl EncounterCard {
-    pub fn new(file_path: &str) -> Option<EncounterCard> {
-        let mut file = File::open(file_path).expect(format!("Failed to open file {}", file_path).as_str());
-        let mut contents = String::new();
-        file.read_to_string(&mut contents).expect(format!("Failed to read file {}", file_path).as_str());
-        let encounter_card: EncounterCard = serde_yaml::from_str(&contents).expect(format!("Failed to parse file {}", file_path).as_str());
-        Some(encounter_card)
+    /// The progress gained by a successful challenge of the choice.
+    pub fn choice_progress(&self, idx: usize) -> usize {
+        self.choices.get(idx)
+            .and_then(|choice| choice.effects.progress)
+            .or_else(|| self.progress.get(idx).copied())
+            .unwrap_or_default()
     }
+
 }
 
-impl TyrantCard {
-    pub fn new(file_path: &str) -> Option<TyrantCard> {
-        let mut file = File::open(file_path).expect(format!("Failed to open file {}", file_path).as_str());
-        let mut contents = String::new();
-        file.read_to_string(&mut contents).expect(format!("Failed to read file {}", file_path).as_str());
-        let tyrant_card: TyrantCard = serde_yaml::from_str(&contents).expect(format!("Failed to parse file {}", file_path).as_str());
-        Some(tyrant_card)
+impl Card for EncounterCard {
+    fn id_mut(&mut self) -> &mut String {
+        &mut self.id
+    }
+
+    /// The pack is the prefix of the id and a plain "tyrant" card type takes the tyrant from the id, unless declared.
+    fn init(&mut self, file: &CardFile) {
+        if self.pack.is_empty() {
+            self.pack = pack_of(&self.id);
+        }
+        if self.card_type == CardType::Tyrant(String::new()) {
+            self.card_type = CardType::Tyrant(tyrant_of(&self.id).unwrap_or_default());
+        }
+        self.source = file.path.to_string_lossy().to_string();
     }
 }
 
-#[derive(Debug)]
+impl Card for TyrantCard {
+    fn id_mut(&mut self) -> &mut String {
+        &mut self.id
+    }
+}
+
+fn check_enough_cards(card_type: &'static str, required: usize, cards: &[EncounterCard]) -> Result<(), ContentError> {
+    if cards.len() < required {
+        return Err(ContentError::NotEnoughCards { card_type, required, available: cards.len() });
+    }
+    Ok(())
+}
+
+/// The options chosen on `SelectBossPage` and `PartySetupPage` to start a new game.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct GameSetup {
+    pub tyrant_id: String,
+    pub seed: Option<u64>, // A random seed is picked if not given
+    #[serde(default)]
+    pub packs: Vec<String>, // The content packs to play with. Empty means all packs.
+    #[serde(default)]
+    pub party: Vec<String>, // The ids of the Gearlocs in play
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EncounterDeck {
+    pub seed: u64,
+    pub rng: ChaCha8Rng,
     pub tyrant_card: TyrantCard,
     pub encounter_cards: Vec<EncounterCard>,
+    pub pool: CardPool,
+    #[serde(default)]
+    pub packs: Vec<String>,
+    #[serde(default)]
+    pub party: Vec<String>, // The ids of the Gearlocs in play. Empty in the saves made before the party setup.
 }
 
 impl EncounterDeck {
-    pub fn new(tyrant_name: &str) -> Self {
+    pub fn new(library: &CardLibrary, setup: &GameSetup) -> Result<Self, ContentError> {
+        let tyrant_name = setup.tyrant_id.as_str();
+        let seed = setup.seed.unwrap_or_else(|| thread_rng().gen());
+        info!("Building encounter decks with seed {}......", seed);
         let mut encounter_cards: Vec<EncounterCard> = Vec::new();
-        let mut rng = thread_rng();
-        let file_path = get_project_root_path() + TYRANT_CARD_GENERAL_PATH + "/" + tyrant_name + ".yaml";
-        let tyrant_card = TyrantCard::new(file_path.as_str()).expect(format!("Tyrant {} doesn't exist", tyrant_name).as_str());
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut pool = library.new_pool(&setup.packs);
+        let tyrant_card = library.tyrant_card(tyrant_name).ok_or_else(|| ContentError::UnknownTyrant(tyrant_name.to_string()))?.clone();
         info!("Building encounter decks with selected tyrant card {:?}......", tyrant_card);
-        // Build tyrant encounter cards. Remove the cards from global deck.
-        let mut tyrant_encounter_card_map = TYRANT_ENCOUNTER_CARDS.lock().unwrap();
-        let tyrant_encounter_cards = tyrant_encounter_card_map.get(tyrant_name).unwrap();
-        let size = tyrant_card.max_days - tyrant_encounter_cards.len() - 3;
-        let pickup = tyrant_encounter_card_map.remove(tyrant_name).unwrap();
-        info!("Selected tyrant encounter cards {:?}......", pickup);
+        // Build tyrant encounter cards. Remove the cards from the card pool.
+        let pickup = pool.tyrant_encounter_cards.remove(tyrant_name).unwrap_or_default();
+        let size = tyrant_card.max_days.checked_sub(pickup.len() + 3).ok_or_else(|| ContentError::NotEnoughDays {
+            tyrant_id: tyrant_card.id.clone(),
+            max_days: tyrant_card.max_days,
+            tyrant_encounters: pickup.len(),
+        })?;
+        info!("Selected tyrant encounter cards {:?}......", card_ids(&pickup));
         encounter_cards.extend(pickup);
 
-        // Build general encounter cards. Remove the cards from global deck.
-        let mut general_encounter_cards = GENERAL_ENCOUNTER_CARDS.lock().unwrap();
+        // Build general encounter cards. Remove the cards from the card pool.
+        let general_encounter_cards = &mut pool.general_encounter_cards;
+        check_enough_cards("general", size, general_encounter_cards)?;
         for _ in 0..size {
             let rand = rng.gen_range(0..general_encounter_cards.len());
             let pickup = general_encounter_cards.remove(rand);
-            info!("Selected general encounter cards {:?}......", pickup);
+            info!("Selected general encounter card {}......", pickup.id);
             encounter_cards.push(pickup);
         }
 
         encounter_cards.shuffle(&mut rng);
 
-        // Build day1, day2, day3 encounter cards. Remove the cards from global deck.
-        let mut day1_cards = DAY1_ENCOUNTER_CARDS.lock().unwrap();
+        // Build day1, day2, day3 encounter cards. Remove the cards from the card pool.
+        let day1_cards = &mut pool.day1_encounter_cards;
+        check_enough_cards("day1", 1, day1_cards)?;
         let day1_rand = rng.gen_range(0..day1_cards.len());
         let day1_card = day1_cards.remove(day1_rand);
-        info!("Selected day1 encounter card {:?}......", day1_card);
+        info!("Selected day1 encounter card {}......", day1_card.id);
         encounter_cards.insert(0, day1_card);
 
-        let mut day2_cards = DAY2_ENCOUNTER_CARDS.lock().unwrap();
+        let day2_cards = &mut pool.day2_encounter_cards;
+        check_enough_cards("day2", 1, day2_cards)?;
         let day2_rand = rng.gen_range(0..day2_cards.len());
         let day2_card = day2_cards.remove(day2_rand);
-        info!("Selected day2 encounter card {:?}......", day2_card);
+        info!("Selected day2 encounter card {}......", day2_card.id);
         encounter_cards.insert(1, day2_card);
 
-        let mut day3_cards = DAY3_ENCOUNTER_CARDS.lock().unwrap();
+        let day3_cards = &mut pool.day3_encounter_cards;
+        check_enough_cards("day3", 1, day3_cards)?;
         let day3_rand = rng.gen_range(0..day3_cards.len());
         let day3_card = day3_cards.remove(day3_rand);
-        info!("Selected day3 encounter card {:?}......", day3_card);
+        info!("Selected day3 encounter card {}......", day3_card.id);
         encounter_cards.insert(2, day3_card);
         let deck = EncounterDeck {
+            seed,
+            rng,
             tyrant_card,
             encounter_cards,
+            pool,
+            packs: setup.packs.clone(),
+            party: setup.party.clone(),
         };
-        info!("Encounter deck built: {:?}", deck);
-        deck
+        info!("Encounter deck built: {:?}", card_ids(&deck.encounter_cards));
+        Ok(deck)
     }
 
     pub fn shuffle(&mut self, shuffle_strategy: ShuffleStrategy, encounter_card: Option<EncounterCard>) {
-        let mut rng = thread_rng();
+        let rng = &mut self.rng;
         match shuffle_strategy {
             ShuffleStrategy::PutCurrentCardTop => {
-                info!("[{:?}] Put {:?} on deck top. Current deck: {:?}", shuffle_strategy, encounter_card, self.encounter_cards);
-                self.encounter_cards.insert(0, encounter_card.expect("Expect the current encounter card!"));
+                let encounter_card = encounter_card.expect("Expect the current encounter card!");
+                info!("[{:?}] Put {} on deck top. Current deck: {:?}", shuffle_strategy, encounter_card.id, card_ids(&self.encounter_cards));
+                self.encounter_cards.insert(0, encounter_card);
             }
             ShuffleStrategy::PutCurrentCardRandom => {
-                info!("[{:?}] Shuffled {:?} into the encounter deck. Current deck: {:?}", shuffle_strategy, encounter_card, self.encounter_cards);
+                let encounter_card = encounter_card.expect("Expect the current encounter card!");
+                info!("[{:?}] Shuffled {} into the encounter deck. Current deck: {:?}", shuffle_strategy, encounter_card.id, card_ids(&self.encounter_cards));
                 let idx = rng.gen_range(0..self.encounter_cards.len());
-                self.encounter_cards.insert(idx, encounter_card.expect("Expect the current encounter card!"));
+                self.encounter_cards.insert(idx, encounter_card);
             }
             ShuffleStrategy::FirstTyrantCardTopAndShuffleRest => {
                 let mut i = 0;
                 while i < self.encounter_cards.len() {
                     if let Some(card) = self.encounter_cards.get(i) {
-                        if card.card_type == "tyrant" {
+                        if matches!(card.card_type, CardType::Tyrant(_)) {
                             break;
                         }
                     }
@@ -247,30 +564,38 @@ impl EncounterDeck {
                 }
                 if i < self.encounter_cards.len() {
                     let card = self.encounter_cards.remove(i);
-                    info!("[{:?}] Put {:?} on the encounter deck top. Current deck: {:?}", shuffle_strategy, encounter_card, self.encounter_cards);
-                    self.encounter_cards.shuffle(&mut rng);
+                    info!("[{:?}] Put {} on the encounter deck top. Current deck: {:?}", shuffle_strategy, card.id, card_ids(&self.encounter_cards));
+                    self.encounter_cards.shuffle(rng);
                     self.encounter_cards.insert(0, card);
                 }
             }
             ShuffleStrategy::PickSpecialCardAndShuffle => {
-                info!("[{:?}] Shuffled {:?} into the encounter deck. Current deck: {:?}", shuffle_strategy, encounter_card, self.encounter_cards);
-                self.encounter_cards.push(encounter_card.expect("Expect the special encounter card!"));
+                let encounter_card = encounter_card.expect("Expect the special encounter card!");
+                info!("[{:?}] Shuffled {} into the encounter deck. Current deck: {:?}", shuffle_strategy, encounter_card.id, card_ids(&self.encounter_cards));
+                self.encounter_cards.push(encounter_card);
                 let mut idx = 0;
                 while idx < self.encounter_cards.len() {
-                    if !&self.encounter_cards[idx].card_type.contains("day") {
+                    if !matches!(self.encounter_cards[idx].card_type, CardType::Day(_)) {
                         break;
                     }
                     idx += 1;
                 }
-                self.encounter_cards[idx..].shuffle(&mut rng);
+                self.encounter_cards[idx..].shuffle(rng);
             }
             ShuffleStrategy::ReplaceTodayEncounterAndShuffleTodayEncounter => {
-                let mut general_cards = GENERAL_ENCOUNTER_CARDS.lock().unwrap();
-                let rand1 = rng.gen_range(1..general_cards.len());
-                let rand2 = rng.gen_range(0..self.encounter_cards.len());
+                let general_cards = &mut self.pool.general_encounter_cards;
+                let encounter_card = encounter_card.expect("Expect the current encounter card!");
+                if general_cards.is_empty() {
+                    // Nothing to replace it with: today's encounter stays on top
+                    info!("[{:?}] No general encounter card left, put {} back on top", shuffle_strategy, encounter_card.id);
+                    self.encounter_cards.insert(0, encounter_card);
+                    return;
+                }
+                let rand1 = rng.gen_range(0..general_cards.len());
+                let rand2 = rng.gen_range(0..=self.encounter_cards.len());
                 let replacement = general_cards.remove(rand1);
-                info!("[{:?}] Replaced today's encounter card with {:?}. Shuffled {:?} into the encounter deck. Current deck: {:?}", shuffle_strategy, replacement, encounter_card, self.encounter_cards);
-                self.encounter_cards.insert(rand2, encounter_card.expect("Expect the current encounter card!"));
+                info!("[{:?}] Replaced today's encounter card with {}. Shuffled {} into the encounter deck. Current deck: {:?}", shuffle_strategy, replacement.id, encounter_card.id, card_ids(&self.encounter_cards));
+                self.encounter_cards.insert(rand2, encounter_card);
                 self.encounter_cards.insert(0, replacement);
             }
         }
@@ -282,13 +607,72 @@ impl EncounterDeck {
             let mut i = finished_encounter_cards.len();
             while i >= day {
                 let card = finished_encounter_cards.pop().unwrap();
-                info!("Insert {} day card: {:?}", i, card);
+                info!("Insert {} day card: {}", i, card.id);
                 self.encounter_cards.insert(0, card);
                 i -= 1;
             }
-            info!("Finished encounter deck rollback. Current encounter deck: {:?}", self.encounter_cards);
+            info!("Finished encounter deck rollback. Current encounter deck: {:?}", card_ids(&self.encounter_cards));
         }
     }
+
+    /// Undo `ReplaceTodayEncounterAndShuffleTodayEncounter` after the encounter deck rollback. The deck rollback put
+    /// the replacement back at the position of its day, so the replacement goes back to the general encounter pool and
+    /// the redrawn card, shuffled into the deck, takes its position. A replacement not in the deck, e.g. in the saves
+    /// made before the replacement was recorded, leaves the redrawn card on top.
+    pub fn rollback_redraw(&mut self, card_id: &str, replacement_id: &str) {
+        let mut idx = 0;
+        if let Some(replacement_idx) = self.encounter_cards.iter().position(|card| card.id == replacement_id) {
+            let replacement = self.encounter_cards.remove(replacement_idx);
+            info!("Put the replacement {} back into the general encounter pool", replacement.id);
+            self.pool.general_encounter_cards.push(replacement);
+            idx = replacement_idx;
+        }
+        let Some(card_idx) = self.encounter_cards.iter().position(|card| card.id == card_id) else { return };
+        let card = self.encounter_cards.remove(card_idx);
+        info!("Put the redrawn card {} back at position {}", card.id, idx);
+        self.encounter_cards.insert(idx, card);
+    }
+}
+
+/// The ids of the cards, for the log output.
+pub fn card_ids(cards: &[EncounterCard]) -> Vec<&str> {
+    cards.iter().map(|card| card.id.as_str()).collect()
+}
+
+/// One resolved encounter of the campaign. The battle log is shown on `GamePage` and drives the rollback.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BattleLog {
+    #[serde(default)]
+    pub card_id: String, // The encounter card id, or the tyrant id for the boss fight
+    pub success: bool,
+    pub title: LocalizedText,
+    pub day: usize,
+    pub progress: usize,
+    pub choice: Choice,
+    #[serde(default)]
+    pub tyrant: bool, // The log of the final boss fight
+    #[serde(default)]
+    pub skill_points: usize, // The skill points given to each Gearloc, taken back by the rollback
+    #[serde(default)]
+    pub health: Vec<i32>, // The health change applied to each Gearloc of the party in order, reverted by the rollback
+    #[serde(default)]
+    pub rolls: Vec<DiceRoll>, // The dice rolled during the encounter
+    #[serde(default)]
+    pub loot: Vec<LootDraw>, // The loot drawn as the rewards, put back by the rollback
+    #[serde(default)]
+    pub redrawn: bool, // The card was shuffled back into the deck and another encounter drawn for the same day
+    #[serde(default)]
+    pub replacement: String, // The id of the general encounter card drawn in place of the redrawn card
+}
+
+/// The result of the whole campaign. The campaign is won or lost by the final boss fight, or lost when the progress
+/// isn't enough to challenge the boss.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CampaignOutcome {
+    #[default]
+    InProgress,
+    Victory,
+    Defeat,
 }
 
 #[derive(Debug)]
@@ -298,4 +682,133 @@ pub enum ShuffleStrategy {
     FirstTyrantCardTopAndShuffleRest,
     PickSpecialCardAndShuffle,
     ReplaceTodayEncounterAndShuffleTodayEncounter,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(seed: u64) -> GameSetup {
+        GameSetup { tyrant_id: "nom".to_string(), seed: Some(seed), packs: Vec::new(), party: Vec::new() }
+    }
+
+    fn ids(cards: &[EncounterCard]) -> Vec<String> {
+        cards.iter().map(|card| card.id.clone()).collect()
+    }
+
+    fn sorted_ids(cards: &[EncounterCard]) -> Vec<String> {
+        let mut ids = ids(cards);
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn same_seed_builds_and_shuffles_the_same_deck() {
+        let library = CardLibrary::load();
+        let mut deck1 = EncounterDeck::new(&library, &setup(42)).unwrap();
+        let mut deck2 = EncounterDeck::new(&library, &setup(42)).unwrap();
+        assert_eq!(ids(&deck1.encounter_cards), ids(&deck2.encounter_cards));
+
+        for deck in [&mut deck1, &mut deck2] {
+            let today_card = deck.encounter_cards.remove(0);
+            deck.shuffle(ShuffleStrategy::PutCurrentCardRandom, Some(today_card));
+            deck.shuffle(ShuffleStrategy::FirstTyrantCardTopAndShuffleRest, None);
+        }
+        assert_eq!(ids(&deck1.encounter_cards), ids(&deck2.encounter_cards));
+
+        let other = EncounterDeck::new(&library, &setup(43)).unwrap();
+        assert_ne!(ids(&deck1.encounter_cards), ids(&other.encounter_cards));
+    }
+
+    #[test]
+    fn games_from_one_library_are_independent() {
+        let library = CardLibrary::load();
+        let general_cards = library.general_encounter_cards.len();
+        let mut deck1 = EncounterDeck::new(&library, &setup(1)).unwrap();
+        let deck2 = EncounterDeck::new(&library, &setup(1)).unwrap();
+        assert_eq!(library.general_encounter_cards.len(), general_cards);
+        assert_eq!(deck1.pool.general_encounter_cards.len(), deck2.pool.general_encounter_cards.len());
+
+        deck1.pool.general_encounter_cards.clear();
+        let today_card = deck1.encounter_cards.remove(0);
+        deck1.shuffle(ShuffleStrategy::PutCurrentCardTop, Some(today_card));
+        assert!(!deck2.pool.general_encounter_cards.is_empty());
+        assert_eq!(ids(&deck1.encounter_cards), ids(&deck2.encounter_cards));
+    }
+
+    #[test]
+    fn redraw_takes_any_general_card_and_keeps_today_without_any() {
+        let library = CardLibrary::load();
+        let mut deck = EncounterDeck::new(&library, &setup(7)).unwrap();
+        let deck_ids = ids(&deck.encounter_cards);
+
+        deck.pool.general_encounter_cards.clear();
+        let today_card = deck.encounter_cards.remove(0);
+        deck.shuffle(ShuffleStrategy::ReplaceTodayEncounterAndShuffleTodayEncounter, Some(today_card));
+        assert_eq!(ids(&deck.encounter_cards), deck_ids);
+
+        deck.pool.general_encounter_cards = library.general_encounter_cards[..1].to_vec();
+        let today_card = deck.encounter_cards.remove(0);
+        deck.shuffle(ShuffleStrategy::ReplaceTodayEncounterAndShuffleTodayEncounter, Some(today_card));
+        assert_eq!(deck.encounter_cards[0].id, library.general_encounter_cards[0].id);
+        assert!(deck.pool.general_encounter_cards.is_empty());
+        assert_eq!(deck.encounter_cards.len(), deck_ids.len() + 1);
+    }
+
+    #[test]
+    fn rollback_redraw_restores_the_deck_and_the_pool() {
+        let library = CardLibrary::load();
+        let mut deck = EncounterDeck::new(&library, &setup(7)).unwrap();
+        let deck_ids = ids(&deck.encounter_cards);
+        let pool_ids = sorted_ids(&deck.pool.general_encounter_cards);
+
+        let today_card = deck.encounter_cards.remove(0);
+        let today_id = today_card.id.clone();
+        deck.shuffle(ShuffleStrategy::ReplaceTodayEncounterAndShuffleTodayEncounter, Some(today_card));
+        let replacement_id = deck.encounter_cards[0].id.clone();
+        assert_ne!(replacement_id, today_id);
+        deck.rollback_redraw(&today_id, &replacement_id);
+        assert_eq!(ids(&deck.encounter_cards), deck_ids);
+        assert_eq!(sorted_ids(&deck.pool.general_encounter_cards), pool_ids);
+    }
+
+    #[test]
+    fn rollback_to_a_day_before_the_redraw_puts_the_redrawn_card_back_on_its_day() {
+        let library = CardLibrary::load();
+        let mut deck = EncounterDeck::new(&library, &setup(7)).unwrap();
+        let deck_ids = ids(&deck.encounter_cards);
+        let pool_ids = sorted_ids(&deck.pool.general_encounter_cards);
+
+        // Day 1 played, day 2 redrawn and its replacement played, day 3 played
+        let mut finished_cards = vec![deck.encounter_cards.remove(0)];
+        let redrawn_card = deck.encounter_cards.remove(0);
+        let redrawn_id = redrawn_card.id.clone();
+        deck.shuffle(ShuffleStrategy::ReplaceTodayEncounterAndShuffleTodayEncounter, Some(redrawn_card));
+        let replacement_id = deck.encounter_cards[0].id.clone();
+        finished_cards.push(deck.encounter_cards.remove(0));
+        finished_cards.push(deck.encounter_cards.remove(0));
+
+        deck.rollback(&mut finished_cards, 1);
+        deck.rollback_redraw(&redrawn_id, &replacement_id);
+        assert!(finished_cards.is_empty());
+        assert_eq!(ids(&deck.encounter_cards), deck_ids);
+        assert_eq!(sorted_ids(&deck.pool.general_encounter_cards), pool_ids);
+    }
+
+    #[test]
+    fn card_type_rejects_unknown_types() {
+        assert_eq!("general".parse::<CardType>().unwrap(), CardType::General);
+        assert_eq!("day2".parse::<CardType>().unwrap(), CardType::Day(2));
+        assert_eq!("tyrant:nom".parse::<CardType>().unwrap(), CardType::Tyrant("nom".to_string()));
+        assert!(matches!("birthday".parse::<CardType>(), Err(ContentError::UnknownCardType(s)) if s == "birthday"));
+        assert!("day0".parse::<CardType>().is_err());
+        assert!("tyrant:".parse::<CardType>().is_err());
+    }
+
+    #[test]
+    fn pack_of_takes_the_longest_known_prefix() {
+        assert_eq!(pack_of("base_set_general_017"), "base_set");
+        assert_eq!(pack_of("40days_nom_001"), "40days");
+        assert_eq!(pack_of("mypack_general_001"), "mypack");
+        assert_eq!(pack_of("single"), "single");
+    }
+}
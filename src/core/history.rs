@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use crate::core::game::{BattleLog, CampaignOutcome};
+use crate::core::i18n::LocalizedText;
+use crate::core::error::StorageError;
+use crate::utils::get_data_dir;
+
+/// Every finished campaign is recorded in the history file, which feeds the statistics page.
+/// A campaign is identified by its campaign id. If a finished campaign is rolled back and finished again,
+/// the record is replaced, so only the final result of each campaign counts.
+static HISTORY_FILE: &str = "history.yaml";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CampaignRecord {
+    pub campaign_id: String,
+    pub finished_at: String,
+    pub tyrant_id: String,
+    pub tyrant_name: LocalizedText,
+    pub seed: u64,
+    pub days: usize,
+    pub max_days: usize,
+    pub progress: usize,
+    pub min_progress: usize,
+    pub outcome: CampaignOutcome,
+    pub battle_logs: Vec<BattleLog>,
+}
+
+/// The statistics of all the campaigns against one tyrant.
+#[derive(Debug, Default)]
+pub struct TyrantStatistics {
+    pub tyrant_name: String,
+    pub games: usize,
+    pub victories: usize,
+    pub defeats: usize,
+    pub average_days: f64,
+    pub max_days: usize,
+    pub average_progress: f64,
+}
+
+/// A new campaign id made of the tyrant and the start time.
+pub fn new_campaign_id(tyrant_id: &str) -> String {
+    format!("{}_{}", tyrant_id, Local::now().format("%Y%m%d%H%M%S"))
+}
+
+pub fn get_history_path() -> PathBuf {
+    get_data_dir().join(HISTORY_FILE)
+}
+
+/// Load the campaign history. A missing history file means no campaign is finished yet.
+pub fn load_history() -> Result<Vec<CampaignRecord>, StorageError> {
+    let path = get_history_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let mut contents = String::new();
+    File::open(&path)?.read_to_string(&mut contents)?;
+    let records: Vec<CampaignRecord> = serde_yaml::from_str(&contents)?;
+    Ok(records)
+}
+
+/// Add the record to the history file, or replace the record of the same campaign.
+pub fn record_campaign(record: CampaignRecord) -> Result<(), StorageError> {
+    let mut records = load_history()?;
+    info!("Recording campaign {} into the history: {:?}", record.campaign_id, record.outcome);
+    match records.iter_mut().find(|r| r.campaign_id == record.campaign_id) {
+        Some(r) => *r = record,
+        None => records.push(record),
+    }
+    let path = get_history_path();
+    std::fs::create_dir_all(get_data_dir())?;
+    let tmp_path = path.with_extension("yaml.tmp");
+    File::create(&tmp_path)?.write_all(serde_yaml::to_string(&records)?.as_bytes())?;
+    std::fs::rename(tmp_path, path)?;
+    Ok(())
+}
+
+/// Win/loss, average days and average progress per tyrant, ordered by the number of games.
+pub fn tyrant_statistics(records: &[CampaignRecord]) -> Vec<TyrantStatistics> {
+    let mut map: HashMap<&str, TyrantStatistics> = HashMap::new();
+    for record in records {
+        let statistics = map.entry(record.tyrant_id.as_str()).or_default();
+        statistics.tyrant_name = record.tyrant_name.to_string();
+        statistics.max_days = record.max_days;
+        statistics.games += 1;
+        match record.outcome {
+            CampaignOutcome::Victory => statistics.victories += 1,
+            CampaignOutcome::Defeat => statistics.defeats += 1,
+            CampaignOutcome::InProgress => {}
+        }
+        statistics.average_days += record.days as f64;
+        statistics.average_progress += record.progress as f64;
+    }
+    let mut statistics: Vec<TyrantStatistics> = map.into_values()
+        .map(|mut s| {
+            s.average_days /= s.games as f64;
+            s.average_progress /= s.games as f64;
+            s
+        })
+        .collect();
+    statistics.sort_by(|a, b| b.games.cmp(&a.games).then(a.tyrant_name.cmp(&b.tyrant_name)));
+    statistics
+}
+
+/// The failed encounter cards as (card id, title, count), the most failed first. Boss fights are not included.
+/// The cards are counted by id, so two cards with the same title are kept apart. Old records without the card id
+/// are counted by title.
+pub fn failed_encounters(records: &[CampaignRecord]) -> Vec<(String, String, usize)> {
+    let mut map: HashMap<&str, (&str, usize)> = HashMap::new();
+    for log in records.iter().flat_map(|r| r.battle_logs.iter()) {
+        if !log.success && !log.tyrant {
+            let key = if log.card_id.is_empty() { log.title.text() } else { log.card_id.as_str() };
+            map.entry(key).or_insert((log.title.text(), 0)).1 += 1;
+        }
+    }
+    let mut failed: Vec<(String, String, usize)> = map.into_iter()
+        .map(|(id, (title, count))| (id.to_string(), title.to_string(), count))
+        .collect();
+    failed.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(&b.0)));
+    failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(tyrant_id: &str, outcome: CampaignOutcome, days: usize, progress: usize, battle_logs: Vec<BattleLog>) -> CampaignRecord {
+        CampaignRecord {
+            campaign_id: format!("{}_{}_{}", tyrant_id, days, progress),
+            finished_at: String::new(),
+            tyrant_id: tyrant_id.to_string(),
+            tyrant_name: tyrant_id.into(),
+            seed: 1,
+            days,
+            max_days: 8,
+            progress,
+            min_progress: 6,
+            outcome,
+            battle_logs,
+        }
+    }
+
+    fn log(card_id: &str, title: &str, success: bool) -> BattleLog {
+        BattleLog { card_id: card_id.to_string(), title: title.into(), success, ..Default::default() }
+    }
+
+    #[test]
+    fn statistics_count_the_outcomes_per_tyrant() {
+        let records = [
+            record("nom", CampaignOutcome::Victory, 8, 7, Vec::new()),
+            record("duster", CampaignOutcome::Defeat, 5, 2, Vec::new()),
+            record("nom", CampaignOutcome::Defeat, 6, 4, Vec::new()),
+            record("nom", CampaignOutcome::Victory, 7, 6, Vec::new()),
+        ];
+        let statistics = tyrant_statistics(&records);
+        let counts: Vec<(&str, usize, usize, usize)> = statistics.iter()
+            .map(|s| (s.tyrant_name.as_str(), s.games, s.victories, s.defeats))
+            .collect();
+        assert_eq!(counts, [("nom", 3, 2, 1), ("duster", 1, 0, 1)]);
+        assert_eq!(statistics[0].average_days, 7.0);
+        assert_eq!(statistics[1].average_progress, 2.0);
+    }
+
+    #[test]
+    fn failed_encounters_are_counted_by_id_the_most_failed_first() {
+        let boss = BattleLog { tyrant: true, ..log("nom", "Brawl with Nom", false) };
+        let records = [
+            record("nom", CampaignOutcome::Defeat, 6, 4, vec![
+                log("base_set_general_002", "Bridge", false),
+                log("base_set_general_003", "Bridge", false),
+                log("base_set_general_004", "Cave", true),
+                boss,
+            ]),
+            record("duster", CampaignOutcome::Defeat, 5, 2, vec![
+                log("base_set_general_003", "Bridge", false),
+                log("", "Old card", false),
+            ]),
+        ];
+        assert_eq!(failed_encounters(&records), [
+            ("base_set_general_003".to_string(), "Bridge".to_string(), 2),
+            ("Old card".to_string(), "Old card".to_string(), 1),
+            ("base_set_general_002".to_string(), "Bridge".to_string(), 1),
+        ]);
+    }
+}
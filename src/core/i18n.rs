@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU8, Ordering};
+use clap::ValueEnum;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// The UI strings and the card text are available in Chinese and English.
+/// - The UI strings are written in place with both translations, e.g. `tr!("继续游戏", "Continue")`, and the one of
+///   the current language is picked when the string is built.
+/// - A card text is either a plain string, which is Chinese, or a map of translations, e.g.
+///   `title: { zh: 离开奥本达, en: Leaving Obendar }`. A missing translation falls back to Chinese.
+///
+/// The current language is global. It is set at startup from the `--lang` flag or the settings, and changed on the home page.
+static LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    Zh,
+    En,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::Zh, Language::En];
+
+    /// The name of the language in the language itself.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::Zh => "中文",
+            Language::En => "English",
+        }
+    }
+
+    /// The language after this one, for switching the language with one key.
+    pub fn next(&self) -> Language {
+        let idx = Language::ALL.iter().position(|language| language == self).unwrap_or_default();
+        Language::ALL[(idx + 1) % Language::ALL.len()]
+    }
+}
+
+pub fn language() -> Language {
+    Language::ALL[LANGUAGE.load(Ordering::Relaxed) as usize]
+}
+
+pub fn set_language(language: Language) {
+    let idx = Language::ALL.iter().position(|l| *l == language).unwrap_or_default();
+    LANGUAGE.store(idx as u8, Ordering::Relaxed);
+}
+
+/// Build a UI string in the current language. Takes the Chinese and the English format strings and their arguments.
+macro_rules! tr {
+    ($zh:literal, $en:literal $(, $arg:expr)* $(,)?) => {
+        match $crate::core::i18n::language() {
+            $crate::core::i18n::Language::Zh => format!($zh $(, $arg)*),
+            $crate::core::i18n::Language::En => format!($en $(, $arg)*),
+        }
+    };
+}
+pub(crate) use tr;
+
+/// A card text with its translations. Displayed in the current language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum LocalizedText {
+    Plain(String), // Chinese only
+    Localized(BTreeMap<Language, String>),
+}
+
+impl LocalizedText {
+    /// A text translated into Chinese and English.
+    pub fn new(zh: &str, en: &str) -> Self {
+        LocalizedText::Localized(BTreeMap::from([(Language::Zh, zh.to_string()), (Language::En, en.to_string())]))
+    }
+
+    /// The text in the given language, if it is translated.
+    pub fn get(&self, language: Language) -> Option<&str> {
+        match self {
+            LocalizedText::Plain(text) => (language == Language::Zh).then_some(text.as_str()),
+            LocalizedText::Localized(texts) => texts.get(&language).map(|text| text.as_str()),
+        }
+    }
+
+    /// The text in the current language, or in Chinese if it isn't translated.
+    pub fn text(&self) -> &str {
+        self.text_in(language())
+    }
+
+    /// The text in the given language, or in Chinese if it isn't translated.
+    pub fn text_in(&self, language: Language) -> &str {
+        self.get(language)
+            .or_else(|| self.get(Language::Zh))
+            .unwrap_or_default()
+    }
+
+    /// The text in the current language with the translation in the other language underneath, if there is one.
+    pub fn bilingual(&self) -> String {
+        let text = self.text();
+        match self.get(language().next()) {
+            Some(other) if !other.is_empty() && other != text => format!("{}\n{}", text, other),
+            _ => text.to_string(),
+        }
+    }
+}
+
+impl Default for LocalizedText {
+    fn default() -> Self {
+        LocalizedText::Plain(String::new())
+    }
+}
+
+impl Display for LocalizedText {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.text())
+    }
+}
+
+impl From<&str> for LocalizedText {
+    fn from(text: &str) -> Self {
+        LocalizedText::Plain(text.to_string())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LocalizedTextRepr {
+    Plain(String),
+    Localized(BTreeMap<Language, String>),
+}
+
+/// An empty YAML value, e.g. `action:` of a choice without action, is an empty text.
+impl<'de> Deserialize<'de> for LocalizedText {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match Option::<LocalizedTextRepr>::deserialize(deserializer)? {
+            None => LocalizedText::default(),
+            Some(LocalizedTextRepr::Plain(text)) => LocalizedText::Plain(text),
+            Some(LocalizedTextRepr::Localized(texts)) => LocalizedText::Localized(texts),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_string_is_chinese() {
+        let text: LocalizedText = serde_yaml::from_str("离开奥本达").unwrap();
+        assert_eq!(text, LocalizedText::Plain("离开奥本达".to_string()));
+        assert_eq!(text.get(Language::Zh), Some("离开奥本达"));
+        assert_eq!(text.get(Language::En), None);
+        let empty: LocalizedText = serde_yaml::from_str("~").unwrap();
+        assert_eq!(empty, LocalizedText::default());
+    }
+
+    #[test]
+    fn map_keeps_each_translation() {
+        let text: LocalizedText = serde_yaml::from_str("{ zh: 离开奥本达, en: Leaving Obendar }").unwrap();
+        assert_eq!(text, LocalizedText::new("离开奥本达", "Leaving Obendar"));
+        assert_eq!(text.text_in(Language::Zh), "离开奥本达");
+        assert_eq!(text.text_in(Language::En), "Leaving Obendar");
+        assert!(serde_yaml::from_str::<LocalizedText>("{ fr: Quitter Obendar }").is_err());
+    }
+
+    #[test]
+    fn missing_translation_falls_back_to_chinese() {
+        let text: LocalizedText = serde_yaml::from_str("zh: 离开奥本达").unwrap();
+        assert_eq!(text.get(Language::En), None);
+        assert_eq!(text.text_in(Language::En), "离开奥本达");
+        let plain: LocalizedText = "白骨之床".into();
+        assert_eq!(plain.text_in(Language::En), "白骨之床");
+        let english_only: LocalizedText = serde_yaml::from_str("en: Leaving Obendar").unwrap();
+        assert_eq!(english_only.text_in(Language::Zh), "");
+    }
+}
@@ -0,0 +1,130 @@
+use rand::SeedableRng;
+use rand::prelude::SliceRandom;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use crate::core::content::Card;
+use crate::core::i18n::LocalizedText;
+
+/// The loot and trove loot cards, read from `config/loot` and `config/trove_loot`, one YAML file per card with the
+/// number of copies in the deck. Every game shuffles both decks with their own RNG seeded by the game seed.
+///
+/// A successful choice that gives loot draws the cards from the top of the decks into the inventory of each Gearloc.
+/// The draws are recorded into the battle log, so a rollback takes the cards back from the inventories and puts them
+/// back on top of the decks in the drawn order, like the encounter deck rollback.
+pub static LOOT_PATH: &str = "/config/loot";
+pub static TROVE_LOOT_PATH: &str = "/config/trove_loot";
+const LOOT_SEED_SALT: u64 = 0x100D;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LootCard {
+    #[serde(default)]
+    pub id: String, // The file name if not declared
+    pub name: LocalizedText,
+    #[serde(default)]
+    pub description: LocalizedText,
+    #[serde(default = "default_copies")]
+    pub copies: usize,
+}
+
+fn default_copies() -> usize {
+    1
+}
+
+impl Card for LootCard {
+    fn id_mut(&mut self) -> &mut String {
+        &mut self.id
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LootKind {
+    Loot,
+    TroveLoot,
+}
+
+/// One loot card drawn by a Gearloc.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct LootDraw {
+    pub gearloc: String,
+    pub kind: LootKind,
+    pub card_id: String,
+}
+
+/// The loot decks of a game, top first. The cards are kept by id.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LootDecks {
+    pub loot: Vec<String>,
+    pub trove_loot: Vec<String>,
+}
+
+impl LootDecks {
+    pub fn new(loot_cards: &[LootCard], trove_loot_cards: &[LootCard], game_seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(game_seed ^ LOOT_SEED_SALT);
+        let mut build = |cards: &[LootCard]| -> Vec<String> {
+            let mut deck: Vec<String> = cards.iter()
+                .flat_map(|card| std::iter::repeat_n(card.id.clone(), card.copies))
+                .collect();
+            deck.shuffle(&mut rng);
+            deck
+        };
+        let decks = LootDecks { loot: build(loot_cards), trove_loot: build(trove_loot_cards) };
+        info!("Loot decks built: {} loot, {} trove loot", decks.loot.len(), decks.trove_loot.len());
+        decks
+    }
+
+    fn deck_mut(&mut self, kind: LootKind) -> &mut Vec<String> {
+        match kind {
+            LootKind::Loot => &mut self.loot,
+            LootKind::TroveLoot => &mut self.trove_loot,
+        }
+    }
+
+    /// Draw the top card for a Gearloc. `None` if the deck is empty.
+    pub fn draw(&mut self, kind: LootKind, gearloc: &str) -> Option<LootDraw> {
+        let deck = self.deck_mut(kind);
+        if deck.is_empty() {
+            return None;
+        }
+        let card_id = deck.remove(0);
+        info!("{} drew {:?} {}", gearloc, kind, card_id);
+        Some(LootDraw { gearloc: gearloc.to_string(), kind, card_id })
+    }
+
+    /// Put the drawn cards back on top of the decks. The draws are given in the drawn order.
+    pub fn rollback(&mut self, draws: &[LootDraw]) {
+        for draw in draws.iter().rev() {
+            info!("Put {:?} {} of {} back on the deck", draw.kind, draw.card_id, draw.gearloc);
+            self.deck_mut(draw.kind).insert(0, draw.card_id.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(id: &str, copies: usize) -> LootCard {
+        LootCard { id: id.to_string(), name: LocalizedText::default(), description: LocalizedText::default(), copies }
+    }
+
+    #[test]
+    fn rollback_puts_the_draws_back_in_order() {
+        let mut decks = LootDecks::new(&[card("rope", 2), card("bandage", 3)], &[card("golden_gear", 1)], 1);
+        assert_eq!(decks.loot.len(), 5);
+        let (loot, trove_loot) = (decks.loot.clone(), decks.trove_loot.clone());
+
+        let draws: Vec<LootDraw> = [LootKind::Loot, LootKind::TroveLoot, LootKind::Loot].into_iter()
+            .filter_map(|kind| decks.draw(kind, "boomer"))
+            .collect();
+        assert_eq!(draws.len(), 3);
+        assert_eq!(draws[0].card_id, loot[0]);
+        assert!(decks.trove_loot.is_empty());
+        assert!(decks.draw(LootKind::TroveLoot, "boomer").is_none());
+
+        decks.rollback(&draws);
+        assert_eq!(decks.loot, loot);
+        assert_eq!(decks.trove_loot, trove_loot);
+    }
+}
@@ -1 +1,13 @@
+pub mod baddie;
+pub mod content;
+pub mod dice;
+pub mod error;
+pub mod export;
 pub mod game;
+pub mod history;
+pub mod i18n;
+pub mod loot;
+pub mod party;
+pub mod save;
+pub mod settings;
+pub mod validate;
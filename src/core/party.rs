@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+use crate::core::content::Card;
+use crate::core::i18n::{Language, LocalizedText};
+use crate::core::loot::LootKind;
+
+/// The Gearlocs are the heroes played by the party. The roster is read from `config/gearlocs`, one YAML file per
+/// Gearloc, built-in or overridden by the content directory like the cards. The party of a game is chosen on
+/// `PartySetupPage` and kept on the encounter deck by the Gearloc ids, since many encounters depend on the party size.
+/// The health, skill points, knocked out state and loot of the Gearlocs in play are tracked on `GamePage`.
+pub static GEARLOC_PATH: &str = "/config/gearlocs";
+pub const MAX_PARTY_SIZE: usize = 4;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Gearloc {
+    #[serde(default)]
+    pub id: String, // The file name if not declared
+    pub name: LocalizedText,
+    pub health: usize, // The starting max health
+    #[serde(default)]
+    pub description: LocalizedText,
+}
+
+impl Card for Gearloc {
+    fn id_mut(&mut self) -> &mut String {
+        &mut self.id
+    }
+}
+
+/// The state of a Gearloc in play, tracked on `GamePage` and kept in the saves.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct GearlocState {
+    pub id: String,
+    pub health: usize,
+    pub max_health: usize,
+    pub skill_points: usize, // Unspent skill points
+    pub knocked_out: bool,
+    #[serde(default)]
+    pub inventory: Vec<String>, // The ids of the loot and trove loot cards, in the drawn order
+}
+
+impl GearlocState {
+    pub fn new(gearloc: &Gearloc) -> Self {
+        GearlocState {
+            id: gearloc.id.clone(),
+            health: gearloc.health,
+            max_health: gearloc.health,
+            skill_points: 0,
+            knocked_out: false,
+            inventory: Vec::new(),
+        }
+    }
+
+    /// Heal one health. A knocked out Gearloc gets back up.
+    pub fn heal(&mut self) {
+        self.health = (self.health + 1).min(self.max_health);
+        self.knocked_out = self.health == 0;
+    }
+
+    /// Lose one health. The Gearloc is knocked out at 0 health.
+    pub fn damage(&mut self) {
+        self.health = self.health.saturating_sub(1);
+        self.knocked_out |= self.health == 0;
+    }
+
+    pub fn raise_max_health(&mut self) {
+        self.max_health += 1;
+    }
+
+    /// Lower the max health, never under 1. The health is capped by the max health.
+    pub fn lower_max_health(&mut self) {
+        self.max_health = self.max_health.saturating_sub(1).max(1);
+        self.health = self.health.min(self.max_health);
+    }
+}
+
+/// The skill points each Gearloc gains from the rewards of a choice, e.g. "每个Gearlock获得2个技能点". `None` if the
+/// rewards don't give skill points, or give different amounts depending on the result, which is left to the players.
+pub fn skill_points_reward(rewards: &LocalizedText) -> Option<usize> {
+    each_gearloc_reward(rewards, &["获得"], "个技能点")
+}
+
+/// The loot or trove loot cards each Gearloc draws from the rewards of a choice, e.g. "每个Gearloc可以抽取1个战利品".
+pub fn loot_reward(rewards: &LocalizedText, kind: LootKind) -> Option<usize> {
+    let unit = match kind {
+        LootKind::Loot => "个战利品",
+        LootKind::TroveLoot => "个宝藏战利品",
+    };
+    each_gearloc_reward(rewards, &["可以抽取", "抽取", "获取"], unit)
+}
+
+/// The amount of "每个Gearloc<verb><amount><unit>" in the Chinese rewards, if the rewards give only one amount.
+fn each_gearloc_reward(rewards: &LocalizedText, verbs: &[&str], unit: &str) -> Option<usize> {
+    let rewards = rewards.get(Language::Zh)?.replace("Gearlock", "Gearloc");
+    let mut amounts: Vec<usize> = rewards.split("每个Gearloc")
+        .skip(1)
+        .filter_map(|rest| {
+            let rest = verbs.iter().find_map(|verb| rest.strip_prefix(verb))?;
+            let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+            rest[digits.len()..].starts_with(unit).then(|| digits.parse().ok()).flatten()
+        })
+        .collect();
+    amounts.dedup();
+    match amounts.as_slice() {
+        [amount] => Some(*amount),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skill_points_accept_both_spellings_of_gearloc() {
+        assert_eq!(skill_points_reward(&"每个Gearlock获得2个技能点。获取1点进度点。".into()), Some(2));
+        assert_eq!(skill_points_reward(&"每个Gearloc获得3个技能点。".into()), Some(3));
+        assert_eq!(skill_points_reward(&"获取1点进度点。".into()), None);
+    }
+
+    #[test]
+    fn loot_doesnt_match_trove_loot() {
+        let loot: LocalizedText = "每个Gearloc可以抽取1个战利品。".into();
+        let trove_loot: LocalizedText = "每个Gearloc可以抽取1个宝藏战利品。".into();
+        assert_eq!(loot_reward(&loot, LootKind::Loot), Some(1));
+        assert_eq!(loot_reward(&loot, LootKind::TroveLoot), None);
+        assert_eq!(loot_reward(&trove_loot, LootKind::Loot), None);
+        assert_eq!(loot_reward(&trove_loot, LootKind::TroveLoot), Some(1));
+    }
+
+    #[test]
+    fn party_rewards_are_left_to_the_players() {
+        let rewards: LocalizedText = "队伍获取1个宝藏战利品。".into();
+        assert_eq!(loot_reward(&rewards, LootKind::TroveLoot), None);
+        assert_eq!(loot_reward(&rewards, LootKind::Loot), None);
+    }
+
+    #[test]
+    fn loot_and_trove_loot_are_read_from_one_text() {
+        let rewards: LocalizedText = "每个Gearlock获得2个技能点。每个Gearloc可以抽取2个战利品。每个Gearloc抽取1个宝藏战利品。".into();
+        assert_eq!(skill_points_reward(&rewards), Some(2));
+        assert_eq!(loot_reward(&rewards, LootKind::Loot), Some(2));
+        assert_eq!(loot_reward(&rewards, LootKind::TroveLoot), Some(1));
+    }
+}
@@ -0,0 +1,223 @@
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use crate::core::game::{BattleLog, CampaignOutcome, EncounterCard, EncounterDeck};
+use crate::core::dice::{DiceRoll, DiceRoller};
+use crate::core::loot::LootDecks;
+use crate::core::party::GearlocState;
+use crate::core::error::StorageError;
+use crate::utils::get_data_dir;
+
+/// Campaign snapshots are stored as YAML files under the save directory, one file per save.
+/// A snapshot covers everything `GamePage` needs to continue the campaign:
+/// 1. The encounter deck (tyrant card, the remaining encounter cards and the card pool of the game).
+/// 2. Days, progress, today's encounter card and the finished encounter cards used by rollback.
+/// 3. The battle logs.
+/// 4. The health, skill points and knocked out state of the Gearlocs in play.
+/// 5. The dice roller and today's dice rolls.
+/// 6. The loot decks. The loot of the Gearlocs is kept in their inventories.
+///
+/// Besides the manual saves, `GamePage` keeps a single autosave which is overwritten after every day transition and rollback.
+/// A session marker file lives next to the saves while the application is running. If the marker is still there on startup,
+/// the previous session didn't exit cleanly (panic or killed terminal) and the autosave can be offered for recovery.
+static SAVE_DIR: &str = "saves";
+static AUTOSAVE_FILE: &str = "autosave.yaml";
+static SESSION_MARKER_FILE: &str = ".session";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub saved_at: String,
+    #[serde(default)]
+    pub campaign_id: String,
+    pub deck: EncounterDeck,
+    pub days: usize,
+    pub progress: usize,
+    pub today_card: Option<EncounterCard>,
+    pub finished_encounter_cards: Vec<EncounterCard>,
+    pub battle_logs: Vec<BattleLog>,
+    #[serde(default)]
+    pub outcome: CampaignOutcome,
+    #[serde(default)]
+    pub party: Vec<GearlocState>,
+    #[serde(default)]
+    pub dice: Option<DiceRoller>,
+    #[serde(default)]
+    pub pending_rolls: Vec<DiceRoll>, // The rolls of today not recorded into the battle log yet
+    #[serde(default)]
+    pub loot: Option<LootDecks>,
+}
+
+impl SaveGame {
+    pub fn read(file_path: &Path) -> Result<SaveGame, StorageError> {
+        let mut file = File::open(file_path)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        let save: SaveGame = serde_yaml::from_str(&contents)?;
+        Ok(save)
+    }
+
+    /// Write the snapshot into a new file of the save directory, named by the tyrant and the save time in milliseconds,
+    /// so two saves in the same second don't overwrite each other.
+    pub fn write(&self) -> Result<PathBuf, StorageError> {
+        let file_name = format!("{}_{}.yaml", self.deck.tyrant_card.id, Local::now().format("%Y%m%d%H%M%S%3f"));
+        self.write_to(file_name.as_str())
+    }
+
+    /// Overwrite the autosave with the snapshot.
+    pub fn write_autosave(&self) -> Result<PathBuf, StorageError> {
+        self.write_to(AUTOSAVE_FILE)
+    }
+
+    fn write_to(&self, file_name: &str) -> Result<PathBuf, StorageError> {
+        let directory = get_save_dir();
+        std::fs::create_dir_all(&directory)?;
+        let file_path = directory.join(file_name);
+        // Write to a temporary file first so that a crash in the middle of writing doesn't corrupt the previous save.
+        let tmp_path = directory.join(format!(".{}.tmp", file_name));
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(serde_yaml::to_string(self)?.as_bytes())?;
+        std::fs::rename(&tmp_path, &file_path)?;
+        info!("Saved the campaign to {:?}", file_path);
+        Ok(file_path)
+    }
+}
+
+/// The local time used to label the saves.
+pub fn timestamp() -> String {
+    Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+pub fn get_save_dir() -> PathBuf {
+    get_data_dir().join(SAVE_DIR)
+}
+
+pub fn get_autosave_path() -> PathBuf {
+    get_save_dir().join(AUTOSAVE_FILE)
+}
+
+/// Find a save by its path, or by its file name in the save directory with or without the extension.
+pub fn resolve_save(name: &str) -> PathBuf {
+    let path = PathBuf::from(name);
+    if path.is_file() {
+        return path;
+    }
+    let path = get_save_dir().join(name);
+    if path.is_file() {
+        return path;
+    }
+    get_save_dir().join(format!("{}.yaml", name))
+}
+
+/// Create the session marker. Returns the autosave to recover if the previous session exited uncleanly.
+pub fn start_session() -> Result<Option<PathBuf>, StorageError> {
+    let directory = get_save_dir();
+    std::fs::create_dir_all(&directory)?;
+    let marker = directory.join(SESSION_MARKER_FILE);
+    let mut recovery = None;
+    if marker.exists() {
+        let mut reason = String::new();
+        File::open(&marker)?.read_to_string(&mut reason)?;
+        info!("The previous session didn't exit cleanly: {}", reason);
+        let autosave = get_autosave_path();
+        if autosave.is_file() {
+            recovery = Some(autosave);
+        }
+    }
+    File::create(&marker)?.write_all(format!("Session started at {}\n", timestamp()).as_bytes())?;
+    Ok(recovery)
+}
+
+/// Remove the session marker. Called when the application exits cleanly.
+pub fn finish_session() -> Result<(), StorageError> {
+    let marker = get_save_dir().join(SESSION_MARKER_FILE);
+    if marker.exists() {
+        std::fs::remove_file(marker)?;
+    }
+    Ok(())
+}
+
+/// Record the crash reason into the session marker, so it can be found in the log of the next session.
+/// The marker is left in place, which makes the next session offer the autosave.
+pub fn record_crash(reason: &str) {
+    let marker = get_save_dir().join(SESSION_MARKER_FILE);
+    if let Ok(mut file) = std::fs::OpenOptions::new().append(true).open(marker) {
+        let _ = file.write_all(format!("Crashed at {}: {}\n", timestamp(), reason).as_bytes());
+    }
+}
+
+/// List all the saves in the save directory, the latest save comes first. Files that can't be parsed are skipped.
+pub fn list_saves() -> Vec<(PathBuf, SaveGame)> {
+    let mut saves = Vec::new();
+    if let Ok(files) = std::fs::read_dir(get_save_dir()) {
+        for file in files.flatten() {
+            let path = file.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "yaml") {
+                match SaveGame::read(&path) {
+                    Ok(save) => saves.push((path, save)),
+                    Err(e) => info!("Skipped broken save {:?}: {}", path, e),
+                }
+            }
+        }
+    }
+    saves.sort_by(|a, b| b.1.saved_at.cmp(&a.1.saved_at));
+    saves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::game::{card_ids, CardLibrary, GameSetup};
+
+    #[test]
+    fn save_loads_back_the_same_campaign() {
+        let library = CardLibrary::load();
+        let setup = GameSetup { tyrant_id: "nom".to_string(), seed: Some(11), packs: Vec::new(), party: Vec::new() };
+        let mut deck = EncounterDeck::new(&library, &setup).unwrap();
+        let finished_encounter_cards: Vec<EncounterCard> = deck.encounter_cards.drain(..2).collect();
+        let today_card = deck.encounter_cards.remove(0);
+        let battle_logs: Vec<BattleLog> = finished_encounter_cards.iter().enumerate()
+            .map(|(idx, card)| BattleLog {
+                card_id: card.id.clone(),
+                success: idx == 0,
+                title: card.title.clone(),
+                day: idx + 1,
+                progress: if idx == 0 { 1 } else { 0 },
+                skill_points: 2,
+                ..Default::default()
+            })
+            .collect();
+        let save = SaveGame {
+            saved_at: timestamp(),
+            campaign_id: "nom_test".to_string(),
+            deck,
+            days: 3,
+            progress: 1,
+            today_card: Some(today_card),
+            finished_encounter_cards,
+            battle_logs,
+            outcome: CampaignOutcome::InProgress,
+            party: Vec::new(),
+            dice: Some(DiceRoller::new(11)),
+            pending_rolls: Vec::new(),
+            loot: None,
+        };
+
+        let path = std::env::temp_dir().join(format!("tmb_save_test_{}.yaml", std::process::id()));
+        File::create(&path).unwrap().write_all(serde_yaml::to_string(&save).unwrap().as_bytes()).unwrap();
+        let loaded = SaveGame::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.deck.seed, 11);
+        assert_eq!((loaded.days, loaded.progress), (3, 1));
+        assert_eq!(card_ids(&loaded.deck.encounter_cards), card_ids(&save.deck.encounter_cards));
+        assert_eq!(loaded.today_card.map(|card| card.id), save.today_card.map(|card| card.id));
+        assert_eq!(card_ids(&loaded.finished_encounter_cards), card_ids(&save.finished_encounter_cards));
+        let logs = |logs: &[BattleLog]| logs.iter()
+            .map(|log| (log.card_id.clone(), log.success, log.day, log.progress, log.skill_points))
+            .collect::<Vec<_>>();
+        assert_eq!(logs(&loaded.battle_logs), logs(&save.battle_logs));
+    }
+}
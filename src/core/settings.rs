@@ -0,0 +1,41 @@
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use crate::core::i18n::Language;
+use crate::core::error::StorageError;
+use crate::utils::get_data_dir;
+
+/// The settings changed in the application and kept for the next start, e.g. the language chosen on the home page.
+static SETTINGS_FILE: &str = "settings.yaml";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Settings {
+    #[serde(default)]
+    pub language: Language,
+    #[serde(default)]
+    pub bilingual: bool, // Show the encounter cards in both languages on the game page
+}
+
+pub fn get_settings_path() -> PathBuf {
+    get_data_dir().join(SETTINGS_FILE)
+}
+
+/// Load the settings. A missing settings file means the default settings.
+pub fn load_settings() -> Result<Settings, StorageError> {
+    let path = get_settings_path();
+    if !path.exists() {
+        return Ok(Settings::default());
+    }
+    let mut contents = String::new();
+    File::open(&path)?.read_to_string(&mut contents)?;
+    Ok(serde_yaml::from_str(&contents)?)
+}
+
+pub fn save_settings(settings: &Settings) -> Result<(), StorageError> {
+    info!("Saving the settings: {:?}", settings);
+    std::fs::create_dir_all(get_data_dir())?;
+    File::create(get_settings_path())?.write_all(serde_yaml::to_string(settings)?.as_bytes())?;
+    Ok(())
+}
@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use crate::core::content::{Card, CardFile, list_card_files, read_card};
+use crate::core::error::ContentError;
+use crate::core::baddie::{Baddie, BADDIE_PATH, creature_types};
+use crate::core::loot::{LootCard, LOOT_PATH, TROVE_LOOT_PATH};
+use crate::core::party::{Gearloc, GEARLOC_PATH};
+use crate::core::game::{CardType, EncounterCard, list_encounter_card_files, tyrant_of, TyrantCard, TYRANT_CARD_GENERAL_PATH, TYRANT_NAMES};
+
+/// One problem of a card file. The line is the line of the broken field if it can be found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.path.to_string_lossy(), line, self.message),
+            None => write!(f, "{}: {}", self.path.to_string_lossy(), self.message),
+        }
+    }
+}
+
+/// The result of a validation pass.
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    pub files: usize,
+    pub problems: Vec<Problem>,
+}
+
+/// Checks every encounter card, tyrant card, Gearloc, baddie and loot card under `config` and reports all the problems at once, instead of
+/// panicking on the first broken file at startup:
+/// - The file is valid YAML and has all the fields of the card.
+/// - An encounter card has one progress value per choice, unless every choice has the progress in its effects.
+/// - The `card_type` is known, see `CardType`, and matches the directory of the card.
+/// - A tyrant id is one of the `TYRANT_NAME_*`, and so is the tyrant of a tyrant encounter card. A plain "tyrant"
+///   card type takes the tyrant from the card id.
+/// - `max_days` of a tyrant leaves room for its tyrant encounter cards plus the three day cards.
+/// - The id of an encounter card, declared or taken from the file name, is used by only one card.
+/// - A Gearloc has some health.
+/// - A baddie is worth some points and has a creature type of a tyrant.
+/// - A loot card has at least one copy.
+pub fn validate_content() -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let tyrant_encounter_ids = check_encounter_cards(list_encounter_card_files(), &mut report);
+    let tyrant_files = list_card_files(TYRANT_CARD_GENERAL_PATH);
+    let creature_types: Vec<String> = tyrant_files.iter()
+        .filter_map(|file| file.as_ref().ok().and_then(|file| read_card::<TyrantCard>(file).ok()))
+        .flat_map(|card| creature_types(&card.creatures))
+        .collect();
+    check_tyrant_cards(tyrant_files, &tyrant_encounter_ids, &mut report);
+    check_gearlocs(list_card_files(GEARLOC_PATH), &mut report);
+    check_baddies(list_card_files(BADDIE_PATH), &creature_types, &mut report);
+    check_loot_cards(list_card_files(LOOT_PATH).into_iter().chain(list_card_files(TROVE_LOOT_PATH)), &mut report);
+    report
+}
+
+/// Check the encounter cards, each with the directory it is read from. Returns the tyrant of each tyrant encounter
+/// card, to check the `max_days` of the tyrants.
+fn check_encounter_cards(files: Vec<(String, Result<CardFile, ContentError>)>, report: &mut ValidationReport) -> Vec<String> {
+    let tyrant_ids = tyrant_ids();
+    let mut tyrant_encounter_ids: Vec<String> = Vec::new();
+    let mut card_ids: HashMap<String, PathBuf> = HashMap::new();
+    for (category, file) in files {
+        report.files += 1;
+        let Some((card, file)) = check_card::<EncounterCard>(file, &mut report.problems) else { continue };
+        let (path, contents) = (&file.path, &file.contents);
+        let card_id = card.id.clone();
+        if let Some(other_path) = card_ids.get(&card_id) {
+            report.problems.push(Problem {
+                path: path.clone(),
+                line: find_key_line(contents, "id"),
+                message: format!("Card id \"{}\" is already used by {}", card_id, other_path.to_string_lossy()),
+            });
+        } else {
+            card_ids.insert(card_id.clone(), path.clone());
+        }
+        if card.progress.len() != card.choices.len() && !card.choices.iter().all(|choice| choice.effects.progress.is_some()) {
+            report.problems.push(Problem {
+                path: path.clone(),
+                line: find_key_line(contents, "progress"),
+                message: format!("{} choices but {} progress values", card.choices.len(), card.progress.len()),
+            });
+        }
+        if card.card_type.directory() != category {
+            report.problems.push(Problem {
+                path: path.clone(),
+                line: find_key_line(contents, "card_type"),
+                message: format!("card_type \"{}\" doesn't match the directory \"{}\"", card.card_type, category),
+            });
+        }
+        if let CardType::Tyrant(tyrant_id) = &card.card_type {
+            let tyrant_id = if tyrant_id.is_empty() { tyrant_of(&card_id).unwrap_or_default() } else { tyrant_id.clone() };
+            if !tyrant_ids.contains(&tyrant_id) {
+                report.problems.push(Problem {
+                    path: path.clone(),
+                    line: find_key_line(contents, "card_type"),
+                    message: format!("Unknown tyrant \"{}\" of card_type, expected one of {}", tyrant_id, tyrant_ids.join(", ")),
+                });
+            }
+            tyrant_encounter_ids.push(tyrant_id);
+        }
+    }
+    tyrant_encounter_ids
+}
+
+/// Check the tyrant cards against the tyrant encounter cards of each tyrant.
+fn check_tyrant_cards(files: Vec<Result<CardFile, ContentError>>, tyrant_encounter_ids: &[String], report: &mut ValidationReport) {
+    let tyrant_ids = tyrant_ids();
+    for file in files {
+        report.files += 1;
+        let Some((card, file)) = check_card::<TyrantCard>(file, &mut report.problems) else { continue };
+        let (path, contents) = (&file.path, &file.contents);
+        if !tyrant_ids.contains(&card.id) {
+            report.problems.push(Problem {
+                path: path.clone(),
+                line: find_key_line(contents, "id"),
+                message: format!("Unknown tyrant id \"{}\", expected one of {}", card.id, tyrant_ids.join(", ")),
+            });
+        }
+        let tyrant_encounters = tyrant_encounter_ids.iter().filter(|id| **id == card.id).count();
+        if card.max_days < tyrant_encounters + 3 {
+            report.problems.push(Problem {
+                path: path.clone(),
+                line: find_key_line(contents, "max_days"),
+                message: format!("max_days {} is less than {} tyrant encounter cards plus 3 day cards", card.max_days, tyrant_encounters),
+            });
+        }
+    }
+}
+
+/// Check that every Gearloc has some health.
+fn check_gearlocs(files: Vec<Result<CardFile, ContentError>>, report: &mut ValidationReport) {
+    for file in files {
+        report.files += 1;
+        let Some((gearloc, file)) = check_card::<Gearloc>(file, &mut report.problems) else { continue };
+        if gearloc.health == 0 {
+            report.problems.push(Problem {
+                path: file.path.clone(),
+                line: find_key_line(&file.contents, "health"),
+                message: "health must be at least 1".to_string(),
+            });
+        }
+    }
+}
+
+/// Check the points of the baddies and that their tags are creature types of the tyrants.
+fn check_baddies(files: Vec<Result<CardFile, ContentError>>, creature_types: &[String], report: &mut ValidationReport) {
+    for file in files {
+        report.files += 1;
+        let Some((baddie, file)) = check_card::<Baddie>(file, &mut report.problems) else { continue };
+        if baddie.points == 0 {
+            report.problems.push(Problem {
+                path: file.path.clone(),
+                line: find_key_line(&file.contents, "points"),
+                message: "points must be at least 1".to_string(),
+            });
+        }
+        if let Some(tag) = baddie.tags.iter().find(|tag| !creature_types.contains(tag)) {
+            report.problems.push(Problem {
+                path: file.path.clone(),
+                line: find_key_line(&file.contents, "tags"),
+                message: format!("Tag \"{}\" isn't a creature type of any tyrant", tag),
+            });
+        }
+    }
+}
+
+/// Check that every loot and trove loot card has at least one copy.
+fn check_loot_cards(files: impl Iterator<Item = Result<CardFile, ContentError>>, report: &mut ValidationReport) {
+    for file in files {
+        report.files += 1;
+        let Some((card, file)) = check_card::<LootCard>(file, &mut report.problems) else { continue };
+        if card.copies == 0 {
+            report.problems.push(Problem {
+                path: file.path.clone(),
+                line: find_key_line(&file.contents, "copies"),
+                message: "copies must be at least 1".to_string(),
+            });
+        }
+    }
+}
+
+/// The tyrant ids, e.g. "nom".
+fn tyrant_ids() -> Vec<String> {
+    TYRANT_NAMES.iter().map(|name| name.to_lowercase()).collect()
+}
+
+/// Parse a card file. Problems are added to the list and `None` is returned.
+fn check_card<T: Card>(file: Result<CardFile, ContentError>, problems: &mut Vec<Problem>) -> Option<(T, CardFile)> {
+    let file = match file {
+        Ok(file) => file,
+        Err(ContentError::Io { path, source }) => {
+            problems.push(Problem { path, line: None, message: format!("Failed to read: {}", source) });
+            return None;
+        }
+        Err(e) => {
+            problems.push(Problem { path: PathBuf::new(), line: None, message: e.to_string() });
+            return None;
+        }
+    };
+    match read_card::<T>(&file) {
+        Ok(card) => Some((card, file)),
+        Err(ContentError::Parse { source, .. }) => {
+            // The error of an unknown card type points to the start of the card, not to the field
+            let line = if source.to_string().contains("card_type") {
+                find_key_line(&file.contents, "card_type")
+            } else {
+                source.location().map(|l| l.line())
+            };
+            problems.push(Problem { path: file.path.clone(), line, message: source.to_string() });
+            None
+        }
+        Err(e) => {
+            problems.push(Problem { path: file.path.clone(), line: None, message: e.to_string() });
+            None
+        }
+    }
+}
+
+/// The one-based line number of a top level key.
+fn find_key_line(contents: &str, key: &str) -> Option<usize> {
+    let prefix = format!("{}:", key);
+    contents.lines().position(|line| line.starts_with(prefix.as_str())).map(|idx| idx + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, contents: &str) -> Result<CardFile, ContentError> {
+        let id = path.rsplit('/').next().unwrap().trim_end_matches(".yaml").to_string();
+        Ok(CardFile { id, path: PathBuf::from(path), built_in: false, contents: contents.to_string() })
+    }
+
+    fn messages(report: &ValidationReport) -> Vec<String> {
+        report.problems.iter().map(|problem| problem.to_string()).collect()
+    }
+
+    #[test]
+    fn progress_must_match_the_choices() {
+        let mut report = ValidationReport::default();
+        check_encounter_cards(vec![("general".to_string(), file("general/mypack_general_001.yaml", "\
+title: Bridge
+story: A bridge
+choices:
+  - description: Cross
+    action: Fight
+    rewards: Nothing
+  - description: Swim
+    action: Fight
+    rewards: Nothing
+remark:
+progress: [1]
+card_type: general"))], &mut report);
+        assert_eq!(report.files, 1);
+        assert_eq!(messages(&report), ["general/mypack_general_001.yaml:11: 2 choices but 1 progress values"]);
+    }
+
+    #[test]
+    fn tyrant_encounter_cards_must_name_a_known_tyrant() {
+        let mut report = ValidationReport::default();
+        let tyrant_encounter_ids = check_encounter_cards(vec![("tyrant".to_string(), file("tyrant/mypack_tyrant_001.yaml", "\
+title: Lair
+story: A lair
+choices: []
+remark:
+card_type: tyrant:smaug"))], &mut report);
+        assert_eq!(tyrant_encounter_ids, ["smaug"]);
+        assert_eq!(report.problems.len(), 1);
+        assert_eq!(report.problems[0].line, Some(5));
+        assert!(report.problems[0].message.starts_with("Unknown tyrant \"smaug\" of card_type"));
+    }
+
+    #[test]
+    fn max_days_must_leave_room_for_the_tyrant_encounters() {
+        let mut report = ValidationReport::default();
+        check_tyrant_cards(vec![file("tyrants/nom.yaml", "\
+id: nom
+name: Nom
+description: A troll
+min_progress: 6
+max_days: 4
+game_length: 1/6
+battle_title: Brawl
+creatures: 巨魔
+battle_mechanism: []
+tyrant_skills: []
+tyrant_die: []")], &["nom".to_string(), "nom".to_string()], &mut report);
+        assert_eq!(messages(&report), ["tyrants/nom.yaml:5: max_days 4 is less than 2 tyrant encounter cards plus 3 day cards"]);
+    }
+
+    #[test]
+    fn card_ids_must_be_unique() {
+        let mut report = ValidationReport::default();
+        let card = "\
+id: mypack_general_001
+title: Bridge
+story: A bridge
+choices: []
+remark:
+card_type: general";
+        check_encounter_cards(vec![
+            ("general".to_string(), file("general/first.yaml", card)),
+            ("general".to_string(), file("general/second.yaml", card)),
+            ("general".to_string(), file("general/mypack_general_001.yaml", &card.replace("id: mypack_general_001\n", ""))),
+        ], &mut report);
+        assert_eq!(report.files, 3);
+        assert_eq!(messages(&report), [
+            "general/second.yaml:1: Card id \"mypack_general_001\" is already used by general/first.yaml",
+            "general/mypack_general_001.yaml: Card id \"mypack_general_001\" is already used by general/first.yaml",
+        ]);
+    }
+}
@@ -1,4 +1,5 @@
 mod app;
+mod cli;
 mod component;
 mod components;
 mod core;
@@ -6,19 +7,30 @@ mod utils;
 
 use std::io::stdout;
 use std::panic;
-use std::path::PathBuf;
+use std::sync::Arc;
 use color_eyre::config::HookBuilder;
 use color_eyre::eyre;
 use color_eyre::eyre::Result;
 use crossterm::terminal::LeaveAlternateScreen;
 use lazy_static::lazy_static;
-use tracing::Level;
+use tracing::{info, Level};
 use tracing_error::ErrorLayer;
-use crate::app::App;
+use clap::Parser;
+use crate::app::{Action, App};
+use crate::cli::{Cli, Command, play_setup, run_command};
+use crate::components::diagnostics_page::DiagnosticsPage;
 use crate::components::game_page::GamePage;
 use crate::components::home_page::HomePage;
+use crate::components::load_game_page::LoadGamePage;
+use crate::components::party_setup_page::PartySetupPage;
 use crate::components::select_boss_page::SelectBossPage;
-use crate::utils::get_project_root_path;
+use crate::components::statistics_page::StatisticsPage;
+use crate::core::content::init_content_dir;
+use crate::core::game::CardLibrary;
+use crate::core::i18n::set_language;
+use crate::core::save::{record_crash, start_session};
+use crate::core::settings::{load_settings, Settings};
+use crate::utils::{get_log_path, get_state_dir};
 use tracing_subscriber::{self, layer::SubscriberExt, util::SubscriberInitExt, Layer};
 
 lazy_static! {
@@ -29,22 +41,57 @@ lazy_static! {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    if let Err(e) = tokio_main().await {
-        eprintln!("{} error: Something went wrong", env!("CARGO_PKG_NAME"));
-        Err(e)
-    } else {
-        Ok(())
+    match tokio_main().await {
+        Err(e) if is_broken_pipe(&e) => Ok(()), // The reader of the console output is gone, e.g. `| head`
+        Err(e) => {
+            eprintln!("{} error: Something went wrong", env!("CARGO_PKG_NAME"));
+            Err(e)
+        }
+        Ok(()) => Ok(()),
     }
 }
 
 async fn tokio_main() -> Result<()> {
+    let cli = Cli::parse();
+    let content_dir = init_content_dir(cli.content_dir)?;
+    let settings = load_settings();
+    set_language(cli.lang.unwrap_or(settings.as_ref().map(|settings| settings.language).unwrap_or_default()));
+    // The console subcommands don't touch the terminal hooks and the log file of the terminal application
+    let (tyrant, seed, packs, party) = match cli.command {
+        None => (None, None, Vec::new(), Vec::new()),
+        Some(Command::Play { tyrant, seed, packs, party }) => (tyrant, seed, packs, party),
+        Some(command) => return run_command(command),
+    };
     initialize_logging()?;
     initialize_panic_handler()?;
+    info!("Resolved the content directory: {:?}", content_dir.content_dir);
+    let settings = settings.unwrap_or_else(|e| {
+        info!("Failed to load the settings, using the default settings: {}", e);
+        Settings::default()
+    });
+    let library = Arc::new(CardLibrary::load());
+    let setup = play_setup(&library, tyrant, seed, packs, party)?;
+    let recovery = start_session()?;
+    let errors = library.errors.iter().map(|e| e.to_string()).collect();
     let mut app = App::new()?;
-    app.register_component(components::home_page::NAME.to_string(), Box::new(HomePage::new()))?;
-    app.register_component(components::select_boss_page::NAME.to_string(), Box::new(SelectBossPage::new()))?;
-    app.register_component(components::game_page::NAME.to_string(), Box::new(GamePage::new()))?;
-    app.run().await?;
+    app.register_component(components::home_page::NAME.to_string(), Box::new(HomePage::new(recovery, errors)))?;
+    app.register_component(components::select_boss_page::NAME.to_string(), Box::new(SelectBossPage::new(library.clone())))?;
+    app.register_component(components::party_setup_page::NAME.to_string(), Box::new(PartySetupPage::new(library.clone())))?;
+    app.register_component(components::game_page::NAME.to_string(), Box::new(GamePage::new(library.clone(), settings.bilingual)))?;
+    app.register_component(components::load_game_page::NAME.to_string(), Box::new(LoadGamePage::new()))?;
+    app.register_component(components::statistics_page::NAME.to_string(), Box::new(StatisticsPage::new()))?;
+    app.register_component(components::diagnostics_page::NAME.to_string(), Box::new(DiagnosticsPage::new(library.clone())))?;
+    if let Some(setup) = setup {
+        info!("Starting the game {:?} from the command line", setup);
+        app.action_sender.send(Action::NewGame(components::game_page::NAME.to_string(), setup))?;
+        app.start_at(components::game_page::NAME);
+    }
+    if let Err(e) = app.run().await {
+        // An error that ends the application is a crash like a panic
+        restore_terminal();
+        record_crash(e.to_string().as_str());
+        return Err(e);
+    }
     Ok(())
 }
 
@@ -53,24 +100,33 @@ async fn tokio_main() -> Result<()> {
 /// Unrecoverable errors are always symptoms of bugs, like trying to access a location beyond the end of an array, and so we want to immediately stop the program.
 /// One approach that makes it easy to show unhandled errors is to use the color_eyre crate to augment the error reporting hooks.
 /// Run `RUST_BACKTRACE=full cargo run` to see the full error stacktrace.
+/// The eyre hook only formats the errors: a Report is also built for the errors the application recovers from.
+/// The panic hook restores the terminal and records the crash into the session marker, so the next start offers to
+/// restore the autosave.
 fn initialize_panic_handler() -> Result<()> {
     let (panic_hook, eyre_hook) = HookBuilder::default().into_hooks();
     let panic_hook = panic_hook.into_panic_hook();
     panic::set_hook(Box::new(move |panic_info| {
-        crossterm::execute!(stdout(), LeaveAlternateScreen).unwrap();
-        crossterm::terminal::disable_raw_mode().unwrap();
+        restore_terminal();
+        record_crash(panic_info.to_string().as_str());
         panic_hook(panic_info);
     }));
-    let eyre_hook = eyre_hook.into_eyre_hook();
-    eyre::set_hook(Box::new(move |error: &(dyn std::error::Error + 'static)| {
-        crossterm::execute!(stdout(), LeaveAlternateScreen).unwrap();
-        crossterm::terminal::disable_raw_mode().unwrap();
-        eyre_hook(error)
-    },
-    ))?;
+    eyre_hook.install()?;
     Ok(())
 }
 
+/// Leave the alternate screen and the raw mode after a crash. Errors are ignored, the terminal may be gone already.
+fn restore_terminal() {
+    if crossterm::terminal::is_raw_mode_enabled().unwrap_or_default() {
+        let _ = crossterm::execute!(stdout(), LeaveAlternateScreen);
+        let _ = crossterm::terminal::disable_raw_mode();
+    }
+}
+
+fn is_broken_pipe(error: &eyre::Report) -> bool {
+    error.downcast_ref::<std::io::Error>().is_some_and(|e| e.kind() == std::io::ErrorKind::BrokenPipe)
+}
+
 /// Initialize logging framework for the project. The log file "tmp.log" will be generated for debug purpose
 /// Usage:
 /// ```
@@ -79,9 +135,8 @@ fn initialize_panic_handler() -> Result<()> {
 /// debug!("hello");
 /// ```
 pub fn initialize_logging() -> Result<()> {
-    let directory = PathBuf::from(get_project_root_path());
-    std::fs::create_dir_all(directory.clone())?;
-    let log_path = directory.join(LOG_FILE.clone());
+    let log_path = get_log_path();
+    std::fs::create_dir_all(get_state_dir())?;
     let log_file = std::fs::File::create(log_path)?;
     std::env::set_var(
         "RUST_LOG",
@@ -22,8 +22,34 @@ pub fn centered_rect(r: Rect, percent_x: u16, percent_y: u16) -> Rect {
         .split(popup_layout[1])[1]
 }
 
-pub fn get_project_root_path() -> String {
-    let project_root = env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".into());
-    let project_root_path = PathBuf::from(project_root);
-    project_root_path.to_string_lossy().into_owned()
+/// The files of the players don't depend on the working directory, so an installed program finds them from anywhere:
+/// - The data directory has the saves, the campaign history, the settings and the exported battle logs. It is
+///   `TMB_DATA_DIR` if set, otherwise `$XDG_DATA_HOME/tmb`, by default `~/.local/share/tmb`.
+/// - The state directory has the log file. It is `$XDG_STATE_HOME/tmb`, by default `~/.local/state/tmb`.
+///
+/// Both fall back to the working directory when neither the XDG variable nor `HOME` is set.
+pub static DATA_DIR_ENV: &str = "TMB_DATA_DIR";
+static APP_DIR_NAME: &str = "tmb";
+
+pub fn get_data_dir() -> PathBuf {
+    non_empty_env(DATA_DIR_ENV).map(PathBuf::from).unwrap_or_else(|| xdg_dir("XDG_DATA_HOME", ".local/share"))
+}
+
+pub fn get_state_dir() -> PathBuf {
+    xdg_dir("XDG_STATE_HOME", ".local/state")
+}
+
+pub fn get_log_path() -> PathBuf {
+    get_state_dir().join(crate::LOG_FILE.as_str())
+}
+
+fn xdg_dir(key: &str, default_in_home: &str) -> PathBuf {
+    non_empty_env(key).map(PathBuf::from)
+        .or_else(|| non_empty_env("HOME").map(|home| PathBuf::from(home).join(default_in_home)))
+        .map(|dir| dir.join(APP_DIR_NAME))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn non_empty_env(key: &str) -> Option<String> {
+    env::var(key).ok().filter(|value| !value.is_empty())
 }
\ No newline at end of file